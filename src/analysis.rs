@@ -0,0 +1,11 @@
+mod blocks;
+mod ecb;
+mod hamming;
+mod modes;
+mod scoring;
+
+pub use blocks::{max_repeated_block, transpose};
+pub use ecb::{detect_aes_128_ecb, is_ecb};
+pub use hamming::hamming_distance_bytes;
+pub use modes::detect_block_mode;
+pub use scoring::{FrequencyTable, NgramLogLikelihood, PrintableAscii, Scorer, UnigramChiSquared};
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+pub fn transpose<T: Copy>(data: &[&[T]]) -> Vec<Vec<T>> {
+    let mut transposed = vec![vec![]; data[0].len()];
+    for row in data {
+        for (j, &value) in row.iter().enumerate() {
+            transposed[j].push(value);
+        }
+    }
+
+    transposed
+}
+
+pub fn max_repeated_block(data: &[u8]) -> u128 {
+    let mut count_chunks = HashMap::<Vec<u8>, u128>::new();
+    for chunk in data.chunks(16) {
+        *count_chunks.entry(chunk.to_vec()).or_insert(1) += 1;
+    }
+
+    count_chunks.into_values().max().unwrap_or_default()
+}
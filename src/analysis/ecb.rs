@@ -0,0 +1,31 @@
+use crate::{error::CryptopalsError, oracles::EncryptionOracle};
+
+use super::max_repeated_block;
+
+pub fn detect_aes_128_ecb(data: &[Vec<u8>]) -> Option<String> {
+    let mut detected_aes_line = String::new();
+    let mut current_max_count = 0;
+    for line in data {
+        let max_count = max_repeated_block(line);
+        if max_count > current_max_count {
+            current_max_count = max_count;
+            detected_aes_line = hex::encode(line);
+        }
+    }
+
+    if detected_aes_line.is_empty() {
+        None
+    } else {
+        Some(detected_aes_line)
+    }
+}
+
+pub fn is_ecb<O>(oracle: &O, block_size: usize) -> Result<bool, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let plain = vec![0; block_size * 100];
+    let ciphertext = oracle.try_encrypt(&plain)?;
+
+    Ok(max_repeated_block(&ciphertext) >= 90)
+}
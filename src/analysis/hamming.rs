@@ -0,0 +1,8 @@
+pub fn hamming_distance_bytes(a: &[u8], b: &[u8]) -> u64 {
+    debug_assert_eq!(a.len(), b.len());
+
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a ^ b).count_ones() as u64)
+        .sum()
+}
@@ -0,0 +1,135 @@
+use crate::{block::BlockMode, error::CryptopalsError, oracles::EncryptionOracle, xor::xor_bytes};
+
+use super::is_ecb;
+
+/// Tells every `BlockMode` apart with a handful of chosen plaintexts.
+///
+/// Needs an oracle that reuses the same key, IV and surrounding data on every call.
+pub fn detect_block_mode<O>(oracle: &O, block_size: usize) -> Result<BlockMode, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let grows_per_byte = oracle.try_encrypt(&[0])?.len() == oracle.try_encrypt(&[])?.len() + 1;
+    if grows_per_byte {
+        detect_stream_mode(oracle, block_size)
+    } else if is_ecb(oracle, block_size)? {
+        Ok(BlockMode::ECB)
+    } else {
+        detect_chaining_mode(oracle, block_size)
+    }
+}
+
+/// Flips one plaintext byte: OFB only changes that byte, CFB garbles from the next block
+/// on and CFB8 garbles every byte after it.
+fn detect_stream_mode<O>(oracle: &O, block_size: usize) -> Result<BlockMode, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let plain = vec![0; block_size * 8];
+    let ciphertext = oracle.try_encrypt(&plain)?;
+
+    for flip in [block_size * 4, block_size * 4 + block_size / 2] {
+        let mut flipped = plain.clone();
+        flipped[flip] = 1;
+        let differences = differing_positions(&ciphertext, &oracle.try_encrypt(&flipped)?);
+
+        let Some(&first) = differences.first() else {
+            continue;
+        };
+        if differences.len() == 1 {
+            return Ok(BlockMode::OFB);
+        }
+
+        // Need at least two bytes left in the block to see whether they changed.
+        let block_end = (first / block_size + 1) * block_size;
+        if block_end - first < 3 {
+            continue;
+        }
+
+        return if differences
+            .iter()
+            .any(|&position| position < block_end && position > first)
+        {
+            Ok(BlockMode::CFB8)
+        } else {
+            Ok(BlockMode::CFB)
+        };
+    }
+
+    Ok(BlockMode::CFB8)
+}
+
+/// Aligns a block `R` after a zero block, then feeds a block that gives the next cipher call
+/// the same input CBC would have given `R`. Only CBC repeats the ciphertext block.
+fn detect_chaining_mode<O>(oracle: &O, block_size: usize) -> Result<BlockMode, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let base = vec![0; block_size * 3];
+    let reference = oracle.try_encrypt(&base)?;
+    let first_changed_block = |position: usize| -> Result<usize, CryptopalsError> {
+        let mut flipped = base.clone();
+        flipped[position] = 1;
+        let ciphertext = oracle.try_encrypt(&flipped)?;
+        Ok(differing_positions(&reference, &ciphertext)
+            .first()
+            .map_or(usize::MAX, |first| first / block_size))
+    };
+
+    let first_block = first_changed_block(0)?;
+    let mut alignment = 0;
+    for position in 1..block_size {
+        if first_changed_block(position)? > first_block {
+            alignment = position;
+            break;
+        }
+    }
+    let zero_block = if alignment == 0 {
+        first_block
+    } else {
+        first_block + 1
+    };
+
+    let r = vec![1; block_size];
+    let query = [vec![0; alignment + block_size], r.clone()].concat();
+    let ciphertext = oracle.try_encrypt(&query)?;
+    let blocks = ciphertext.chunks(block_size).collect::<Vec<&[u8]>>();
+
+    let cbc_input = xor_bytes(&r, blocks[zero_block]);
+    let replay_block = xor_bytes(blocks[zero_block + 1], &cbc_input);
+    let replayed = oracle.try_encrypt(&[query, replay_block].concat())?;
+    let replayed_blocks = replayed.chunks(block_size).collect::<Vec<&[u8]>>();
+
+    if replayed_blocks[zero_block + 2] == blocks[zero_block + 1] {
+        Ok(BlockMode::CBC)
+    } else {
+        Ok(BlockMode::PCBC)
+    }
+}
+
+fn differing_positions(a: &[u8], b: &[u8]) -> Vec<usize> {
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(position, _)| position)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{oracles::random_mode::fixed_mode_oracle, random::SeededRng};
+
+    use super::*;
+
+    #[test]
+    fn detect_block_mode_works() {
+        let mut rng = SeededRng::from_env();
+        for mode in BlockMode::ALL {
+            (0..20).for_each(|_| {
+                let (expected_mode, oracle) = fixed_mode_oracle(&mut rng, &[mode]);
+                assert_eq!(expected_mode, detect_block_mode(&oracle, 16).unwrap());
+            });
+        }
+    }
+}
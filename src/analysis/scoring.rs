@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+const ENGLISH_CORPUS: &str = include_str!("../../resources/english.txt");
+
+/// Rates how much some data looks like the expected plaintext. Lower scores are better.
+pub trait Scorer {
+    fn score(&self, data: &[u8]) -> f64;
+}
+
+/// Relative frequency of every byte value, counted case-insensitively.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencyTable {
+    frequencies: [f64; 256],
+}
+
+impl FrequencyTable {
+    pub fn english() -> Self {
+        let known_frequencies = [
+            (b' ', 20.0),
+            (b'e', 12.7),
+            (b't', 9.1),
+            (b'a', 8.2),
+            (b'o', 7.5),
+            (b'i', 7.0),
+            (b'n', 6.7),
+            (b's', 6.3),
+            (b'h', 6.1),
+            (b'r', 6.0),
+            (b'd', 4.3),
+            (b'l', 4.0),
+            (b'c', 2.8),
+            (b'u', 2.8),
+            (b'm', 2.4),
+            (b'w', 2.4),
+            (b'f', 2.2),
+            (b'g', 2.0),
+            (b'y', 2.0),
+            (b'p', 1.9),
+            (b'b', 1.5),
+            (b'v', 0.98),
+            (b'k', 0.77),
+            (b'x', 0.15),
+            (b'j', 0.15),
+            (b'q', 0.095),
+            (b'z', 0.074),
+            (b'\n', 1.0),
+            (b'.', 0.65),
+            (b',', 0.6),
+            (b'\'', 0.25),
+            (b'"', 0.2),
+            (b'-', 0.15),
+        ];
+
+        let mut frequencies = [0.000001; 256];
+        frequencies[0x20..0x7f].fill(0.01);
+        for (byte, frequency) in known_frequencies {
+            frequencies[byte as usize] = frequency;
+        }
+
+        Self::normalized(frequencies)
+    }
+
+    pub fn train(corpus: &[u8]) -> Self {
+        let mut frequencies = [0.01; 256];
+        for byte in corpus.to_ascii_lowercase() {
+            frequencies[byte as usize] += 1.0;
+        }
+
+        Self::normalized(frequencies)
+    }
+
+    pub fn frequency(&self, byte: u8) -> f64 {
+        self.frequencies[byte.to_ascii_lowercase() as usize]
+    }
+
+    fn normalized(mut frequencies: [f64; 256]) -> Self {
+        frequencies[b'A' as usize..=b'Z' as usize].fill(0.0);
+
+        let total = frequencies.iter().sum::<f64>();
+        frequencies
+            .iter_mut()
+            .for_each(|frequency| *frequency /= total);
+
+        Self { frequencies }
+    }
+}
+
+impl Default for FrequencyTable {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// Pearson's chi-squared statistic of the byte counts against a frequency table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnigramChiSquared {
+    table: FrequencyTable,
+}
+
+impl UnigramChiSquared {
+    pub fn new(table: FrequencyTable) -> Self {
+        Self { table }
+    }
+}
+
+impl Scorer for UnigramChiSquared {
+    fn score(&self, data: &[u8]) -> f64 {
+        if data.is_empty() {
+            return f64::MAX;
+        }
+
+        let mut counts = [0usize; 256];
+        for byte in data.to_ascii_lowercase() {
+            counts[byte as usize] += 1;
+        }
+
+        let total = data.len() as f64;
+        (0..=255u8)
+            .filter(|byte| !byte.is_ascii_uppercase())
+            .map(|byte| {
+                let expected = self.table.frequency(byte) * total;
+                let observed = counts[byte as usize] as f64;
+                (observed - expected).powi(2) / expected
+            })
+            .sum()
+    }
+}
+
+/// Average negative log-likelihood of the n-grams in the data under a trained model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NgramLogLikelihood {
+    n: usize,
+    log_probabilities: HashMap<Vec<u8>, f64>,
+    unseen_log_probability: f64,
+}
+
+impl NgramLogLikelihood {
+    pub fn bigrams(corpus: &[u8]) -> Self {
+        Self::train(2, corpus)
+    }
+
+    pub fn trigrams(corpus: &[u8]) -> Self {
+        Self::train(3, corpus)
+    }
+
+    pub fn english_bigrams() -> Self {
+        Self::bigrams(ENGLISH_CORPUS.as_bytes())
+    }
+
+    pub fn english_trigrams() -> Self {
+        Self::trigrams(ENGLISH_CORPUS.as_bytes())
+    }
+
+    fn train(n: usize, corpus: &[u8]) -> Self {
+        let corpus = corpus.to_ascii_lowercase();
+
+        let mut counts = HashMap::<Vec<u8>, usize>::new();
+        for ngram in corpus.windows(n) {
+            *counts.entry(ngram.to_vec()).or_default() += 1;
+        }
+
+        let total = counts.values().sum::<usize>().max(1) as f64;
+        let log_probabilities = counts
+            .into_iter()
+            .map(|(ngram, count)| (ngram, (count as f64 / total).ln()))
+            .collect();
+
+        Self {
+            n,
+            log_probabilities,
+            unseen_log_probability: (0.01 / total).ln(),
+        }
+    }
+}
+
+impl Scorer for NgramLogLikelihood {
+    fn score(&self, data: &[u8]) -> f64 {
+        if data.len() < self.n {
+            return f64::MAX;
+        }
+
+        let data = data.to_ascii_lowercase();
+        let ngrams = data.windows(self.n);
+        let num_ngrams = ngrams.len() as f64;
+        let log_likelihood = ngrams
+            .map(|ngram| {
+                self.log_probabilities
+                    .get(ngram)
+                    .copied()
+                    .unwrap_or(self.unseen_log_probability)
+            })
+            .sum::<f64>();
+
+        -log_likelihood / num_ngrams
+    }
+}
+
+/// Fraction of bytes that are neither printable ASCII nor common whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrintableAscii;
+
+impl Scorer for PrintableAscii {
+    fn score(&self, data: &[u8]) -> f64 {
+        if data.is_empty() {
+            return f64::MAX;
+        }
+
+        let non_printable = data
+            .iter()
+            .filter(|&&byte| !(byte.is_ascii_graphic() || b" \t\r\n".contains(&byte)))
+            .count();
+
+        non_printable as f64 / data.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: &[u8] = b"Cooking MC's like a pound of bacon";
+    const GARBAGE: &[u8] = b"\x1b77316?x\x15\x1b\x7f+x413=x9x(7-6<x7>x:9;76";
+
+    #[test]
+    fn scorers_prefer_english() {
+        let scorers: [Box<dyn Scorer>; 5] = [
+            Box::new(UnigramChiSquared::default()),
+            Box::new(UnigramChiSquared::new(FrequencyTable::train(
+                ENGLISH_CORPUS.as_bytes(),
+            ))),
+            Box::new(NgramLogLikelihood::english_bigrams()),
+            Box::new(NgramLogLikelihood::english_trigrams()),
+            Box::new(PrintableAscii),
+        ];
+
+        for scorer in scorers {
+            assert!(scorer.score(ENGLISH) < scorer.score(GARBAGE));
+        }
+    }
+
+    #[test]
+    fn frequency_table_train_works() {
+        let table = FrequencyTable::train(b"aaAb");
+        assert!(table.frequency(b'a') > table.frequency(b'b'));
+        assert_eq!(table.frequency(b'a'), table.frequency(b'A'));
+        assert!(table.frequency(b'z') > 0.0);
+    }
+
+    #[test]
+    fn printable_ascii_works() {
+        assert_eq!(0.0, PrintableAscii.score(b"Hello,\tworld!\n"));
+        assert_eq!(0.5, PrintableAscii.score(b"ab\x00\xff"));
+    }
+}
@@ -0,0 +1,32 @@
+mod cbc_bitflipping;
+mod cbc_padding_oracle;
+mod ecb_byte_at_a_time;
+mod ecb_cut_and_paste;
+mod fixed_nonce_ctr;
+mod mt19937;
+mod probe;
+mod repeating_key_xor;
+mod single_byte_xor;
+
+pub use cbc_bitflipping::cbc_bitflipping_attack;
+pub use cbc_padding_oracle::cbc_padding_oracle_attack;
+pub use ecb_byte_at_a_time::{
+    attack_ecb_one_byte_at_a_time, attack_ecb_one_byte_at_a_time_prefix,
+    attack_ecb_one_byte_at_a_time_with_order, brute_force_ciphertext_block, GuessOrder,
+};
+pub use ecb_cut_and_paste::ecb_cut_and_paste_attack;
+pub use fixed_nonce_ctr::{ColumnAlignment, FixedNonceCtrBreak};
+pub use mt19937::{
+    clone_mt19937, clone_mt19937_64, detect_time_seeded_token, recover_mt19937_key,
+    recover_time_seed,
+};
+pub use probe::{compute_block_size_and_padding_length, prefix_length};
+pub use repeating_key_xor::{
+    break_repeating_key_xor_base64, break_repeating_key_xor_bytes, break_repeating_key_xor_hex,
+    rank_keysizes, KeysizeCandidate, RepeatingKeyDecryptMetadata,
+};
+pub use single_byte_xor::{
+    break_single_xor_base64, break_single_xor_bytes, break_single_xor_hex,
+    detect_single_xor_ciphertext_base64, detect_single_xor_ciphertext_hex, rank_single_xor_base64,
+    rank_single_xor_bytes, rank_single_xor_hex, DecryptMetadata, RankedCandidates,
+};
@@ -0,0 +1,32 @@
+use crate::{error::CryptopalsError, oracles::EncryptionOracle};
+
+use super::{compute_block_size_and_padding_length, prefix_length};
+
+/// Forges a ciphertext containing `;admin=true;` from an oracle that escapes `;` and `=`
+/// in the user data it encrypts with CBC.
+pub fn cbc_bitflipping_attack<O>(oracle: &O) -> Result<Vec<u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let (block_size, _) = compute_block_size_and_padding_length(oracle)?;
+
+    // A whole block of filler right after the prefix is flipped, so the prefix stays intact
+    // and the payload in the next block picks up the flips.
+    let full_prefix_length = prefix_length(oracle, block_size)?;
+    let flipped_block = full_prefix_length.div_ceil(block_size) * block_size;
+
+    let mut crafted_input = vec![b'a'; flipped_block - full_prefix_length + block_size];
+    crafted_input.extend_from_slice(b"XadminYtrueX");
+
+    let mut crafted_ciphertext = oracle.try_encrypt(&crafted_input)?;
+    if crafted_ciphertext.len() < flipped_block + 2 * block_size {
+        return Err(CryptopalsError::OracleFailure(
+            "Ciphertext shorter than the crafted input".to_string(),
+        ));
+    }
+    crafted_ciphertext[flipped_block] ^= b';' ^ b'X';
+    crafted_ciphertext[flipped_block + 6] ^= b'=' ^ b'Y';
+    crafted_ciphertext[flipped_block + 11] ^= b';' ^ b'X';
+
+    Ok(crafted_ciphertext)
+}
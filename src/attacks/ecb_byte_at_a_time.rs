@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use crate::{analysis::is_ecb, error::CryptopalsError, oracles::EncryptionOracle};
+
+use super::{compute_block_size_and_padding_length, prefix_length};
+
+/// The order in which candidates for each secret byte are sent to the oracle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GuessOrder {
+    /// Every byte value in a single query.
+    #[default]
+    AllBytes,
+    /// Printable characters first, most common first, then the other ASCII bytes and then the
+    /// rest, each only when nothing before matched. Sends fewer bytes when the secret is text
+    /// and never sends non-ASCII bytes for an ASCII secret.
+    PrintableFirst,
+}
+
+/// Printable characters ordered roughly by how often they show up in English text.
+const LIKELY_CHARACTERS: &[u8] =
+    b" etaoinsrhldcumfpgwybvkxjqzETAOINSRHLDCUMFPGWYBVKXJQZ.,'\"-?!:;0123456789\n";
+
+impl GuessOrder {
+    fn batches(self) -> Vec<Vec<u8>> {
+        match self {
+            GuessOrder::AllBytes => vec![(0..=255).collect()],
+            GuessOrder::PrintableFirst => {
+                let mut printable = LIKELY_CHARACTERS.to_vec();
+                printable.extend(
+                    (0..=255u8)
+                        .filter(|c| c.is_ascii_graphic() || c.is_ascii_whitespace())
+                        .filter(|c| !LIKELY_CHARACTERS.contains(c)),
+                );
+                let (ascii, rest) = (0..=255u8)
+                    .filter(|c| !printable.contains(c))
+                    .partition(u8::is_ascii);
+
+                vec![printable, ascii, rest]
+            }
+        }
+    }
+}
+
+pub fn attack_ecb_one_byte_at_a_time<O>(oracle: &O) -> Result<String, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    attack_ecb_one_byte_at_a_time_with_order(oracle, GuessOrder::default())
+}
+
+/// Recovers each secret byte with a single query holding one block per candidate, matched
+/// against the secret encrypted at each of the `block_size` shifts, which is fetched once.
+pub fn attack_ecb_one_byte_at_a_time_with_order<O>(
+    oracle: &O,
+    order: GuessOrder,
+) -> Result<String, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let (block_size, padding_length) = compute_block_size_and_padding_length(oracle)?;
+    if !is_ecb(oracle, block_size)? {
+        return Err(CryptopalsError::OracleFailure(
+            "Data not encryped with ECB".to_string(),
+        ));
+    }
+
+    let shifted_ciphertexts = (0..block_size)
+        .map(|shift| oracle.try_encrypt(&vec![0; block_size - 1 - shift]))
+        .collect::<Result<Vec<Vec<u8>>, CryptopalsError>>()?;
+    let batches = order.batches();
+
+    let mut plain = vec![0; block_size - 1];
+    let num_target_bytes = shifted_ciphertexts[block_size - 1].len() - padding_length;
+    for i in 0..num_target_bytes {
+        let known = &plain[plain.len() - (block_size - 1)..];
+        let start = (i / block_size) * block_size;
+        let target = &shifted_ciphertexts[i % block_size][start..start + block_size];
+        let character = guess_byte(oracle, &[], 0, known, target, &batches, false)?.ok_or(
+            CryptopalsError::OracleFailure(format!("No dictionary entry for byte {i}")),
+        )?;
+
+        plain.push(character);
+    }
+
+    Ok(String::from_utf8(plain[block_size - 1..].to_vec())?)
+}
+
+/// Like `attack_ecb_one_byte_at_a_time`, behind a prefix of unknown length.
+///
+/// The end of the secret is found from its padding rather than from ciphertext lengths. If the
+/// oracle stops answering consistently partway through, the error is a `PartialRecovery`
+/// holding the bytes recovered so far.
+pub fn attack_ecb_one_byte_at_a_time_prefix<O>(oracle: &O) -> Result<Vec<u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let (block_size, _) = compute_block_size_and_padding_length(oracle)?;
+    if !is_ecb(oracle, block_size)? {
+        return Err(CryptopalsError::OracleFailure(
+            "Data not encryped with ECB".to_string(),
+        ));
+    }
+
+    let prefix_length = prefix_length(oracle, block_size)?;
+    let prefix_blocks = prefix_length.div_ceil(block_size);
+    let alignment = vec![0; prefix_blocks * block_size - prefix_length];
+
+    let shifted_ciphertexts = (0..block_size)
+        .map(|shift| oracle.try_encrypt(&vec![0; alignment.len() + block_size - 1 - shift]))
+        .collect::<Result<Vec<Vec<u8>>, CryptopalsError>>()?;
+    // Printable text first also keeps the queries ASCII for oracles that reject anything else.
+    let batches = GuessOrder::PrintableFirst.batches();
+
+    let mut plain = vec![0; block_size - 1];
+    loop {
+        let i = plain.len() - (block_size - 1);
+        let known = &plain[i..];
+        let start = (prefix_blocks + (i / block_size)) * block_size;
+        let target = shifted_ciphertexts[i % block_size].get(start..start + block_size);
+        let guess = match target {
+            Some(target) => guess_byte(
+                oracle,
+                &alignment,
+                prefix_blocks,
+                known,
+                target,
+                &batches,
+                true,
+            ),
+            None => Ok(None),
+        };
+        let end_of_secret = match (&guess, target) {
+            // Right after the secret the target block ends in a single 0x01 padding byte. One
+            // byte further it ends in 0x02 0x02, which no guess matches.
+            (Ok(None), Some(target)) if i > 0 && known.last() == Some(&1) => {
+                is_padding_block(oracle, &alignment, prefix_blocks, known, target)
+            }
+            _ => Ok(false),
+        };
+
+        match (guess, end_of_secret) {
+            (Ok(Some(character)), _) => plain.push(character),
+            (_, Ok(true)) => {
+                plain.pop();
+                break;
+            }
+            (Ok(None), Ok(false)) => {
+                return Err(CryptopalsError::PartialRecovery(
+                    plain[block_size - 1..].to_vec(),
+                    Box::new(CryptopalsError::OracleFailure(format!(
+                        "No dictionary entry for byte {i}"
+                    ))),
+                ))
+            }
+            (Err(error), _) | (_, Err(error)) => {
+                return Err(CryptopalsError::PartialRecovery(
+                    plain[block_size - 1..].to_vec(),
+                    Box::new(error),
+                ))
+            }
+        }
+    }
+
+    Ok(plain.split_off(block_size - 1))
+}
+
+/// Encrypts `known` followed by each candidate as consecutive blocks of one query, batch by
+/// batch, and returns the candidate whose block matches `target`.
+///
+/// `alignment` fills the last of the oracle's `prefix_blocks`. With `skip_rejected`, batches the
+/// oracle fails on are skipped, as some oracles reject bytes outside a character set.
+fn guess_byte<O>(
+    oracle: &O,
+    alignment: &[u8],
+    prefix_blocks: usize,
+    known: &[u8],
+    target: &[u8],
+    batches: &[Vec<u8>],
+    skip_rejected: bool,
+) -> Result<Option<u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let block_size = target.len();
+    for batch in batches {
+        let candidates = batch
+            .iter()
+            .flat_map(|&candidate| [known, &[candidate]].concat())
+            .collect::<Vec<u8>>();
+        let ciphertext = match oracle.try_encrypt(&[alignment, &candidates].concat()) {
+            Ok(ciphertext) => ciphertext,
+            Err(CryptopalsError::OracleFailure(_)) if skip_rejected => continue,
+            Err(error) => return Err(error),
+        };
+
+        let found = ciphertext
+            .chunks(block_size)
+            .skip(prefix_blocks)
+            .zip(batch)
+            .find(|(block, _)| *block == target)
+            .map(|(_, &candidate)| candidate);
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether `target` encrypts `known` without its final 0x01 followed by 0x02 0x02, the block
+/// seen one byte past the end of the secret.
+fn is_padding_block<O>(
+    oracle: &O,
+    alignment: &[u8],
+    prefix_blocks: usize,
+    known: &[u8],
+    target: &[u8],
+) -> Result<bool, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let block = [&known[..known.len() - 1], &[2, 2]].concat();
+    let ciphertext = oracle.try_encrypt(&[alignment, &block].concat())?;
+    let start = prefix_blocks * target.len();
+
+    Ok(ciphertext.get(start..start + target.len()) == Some(target))
+}
+
+pub fn brute_force_ciphertext_block<O>(
+    oracle: &O,
+    prefix: &[u8],
+    block_position: usize,
+    block_size: usize,
+) -> Result<HashMap<Vec<u8>, u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let mut encrypted_block_to_character = HashMap::new();
+    for i in 0..=255u8 {
+        let prefix_with_character = [prefix, &[i]].concat().to_vec();
+        let encrypted_data = oracle.try_encrypt(&prefix_with_character)?;
+        let start = block_position * block_size;
+        let end = start + block_size;
+        let encrypted_block = encrypted_data[start..end].to_vec();
+        encrypted_block_to_character.insert(encrypted_block, i);
+    }
+
+    Ok(encrypted_block_to_character)
+}
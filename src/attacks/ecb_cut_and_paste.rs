@@ -0,0 +1,51 @@
+use crate::{analysis::is_ecb, error::CryptopalsError, oracles::EncryptionOracle};
+
+use super::compute_block_size_and_padding_length;
+
+pub fn ecb_cut_and_paste_attack<O>(oracle: &O) -> Result<Vec<u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let (block_size, original_padding_length) = compute_block_size_and_padding_length(oracle)?;
+    if !is_ecb(oracle, block_size)? {
+        return Err(CryptopalsError::OracleFailure(
+            "Data not encryped with ECB".to_string(),
+        ));
+    }
+
+    let admin_block_padding = vec![(block_size - 5) as u8; block_size - 5];
+    let admin_block = ["admin".as_bytes(), &admin_block_padding].concat();
+
+    let crafted_ciphertext_block = craft_admin_ciphertext_block(oracle, block_size, admin_block)?;
+
+    let crafted_input = vec![0; original_padding_length + "user".len()];
+    let ciphertext = oracle.try_encrypt(&crafted_input)?;
+    let ciphertext_without_last_block = ciphertext[..ciphertext.len() - block_size].to_vec();
+
+    Ok([ciphertext_without_last_block, crafted_ciphertext_block].concat())
+}
+
+fn craft_admin_ciphertext_block<O>(
+    oracle: &O,
+    block_size: usize,
+    admin_block: Vec<u8>,
+) -> Result<Vec<u8>, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    for i in 0..block_size {
+        let crafted_input = [vec![0; i], admin_block.clone(), admin_block.clone()].concat();
+        let ciphertext = oracle.try_encrypt(&crafted_input)?;
+        let ciphertext_blocks = ciphertext.chunks(block_size).collect::<Vec<&[u8]>>();
+
+        for j in 1..ciphertext_blocks.len() {
+            if ciphertext_blocks[j - 1] == ciphertext_blocks[j] {
+                return Ok(ciphertext_blocks[j].to_vec());
+            }
+        }
+    }
+
+    Err(CryptopalsError::OracleFailure(
+        "Admin block never lined up with a block boundary".to_string(),
+    ))
+}
@@ -0,0 +1,247 @@
+use crate::{
+    analysis::{transpose, Scorer},
+    error::CryptopalsError,
+    xor::xor_bytes,
+};
+
+use super::rank_single_xor_bytes;
+
+/// Which bytes of the ciphertexts feed the keystream columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    /// Cut every ciphertext to the shortest one, so all columns have the same height.
+    Truncate,
+    /// Use every byte. Later columns are broken from fewer ciphertexts.
+    Longest,
+}
+
+/// Keystream shared by ciphertexts encrypted under the same key and nonce, recovered one
+/// column at a time as single-byte xor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedNonceCtrBreak {
+    ciphertexts: Vec<Vec<u8>>,
+    keystream: Vec<u8>,
+    confidence: Vec<f64>,
+}
+
+impl FixedNonceCtrBreak {
+    pub fn new(
+        ciphertexts: &[Vec<u8>],
+        scorer: &dyn Scorer,
+        alignment: ColumnAlignment,
+    ) -> Result<Self, CryptopalsError> {
+        if ciphertexts.is_empty() {
+            return Err(CryptopalsError::InvalidDataLength(0));
+        }
+
+        let columns = match alignment {
+            ColumnAlignment::Truncate => {
+                let length = ciphertexts.iter().map(Vec::len).min().unwrap_or_default();
+                let rows = ciphertexts
+                    .iter()
+                    .map(|ciphertext| &ciphertext[..length])
+                    .collect::<Vec<&[u8]>>();
+                transpose(&rows)
+            }
+            ColumnAlignment::Longest => {
+                let length = ciphertexts.iter().map(Vec::len).max().unwrap_or_default();
+                (0..length)
+                    .map(|i| {
+                        ciphertexts
+                            .iter()
+                            .filter_map(|ciphertext| ciphertext.get(i).copied())
+                            .collect()
+                    })
+                    .collect()
+            }
+        };
+
+        let (keystream, confidence) = columns
+            .iter()
+            .map(|column| {
+                let ranking = rank_single_xor_bytes(column, scorer);
+                let key = ranking.best().map_or(0, |best| best.key);
+                (key, ranking.confidence_margin().unwrap_or_default())
+            })
+            .unzip();
+
+        Ok(Self {
+            ciphertexts: ciphertexts.to_vec(),
+            keystream,
+            confidence,
+        })
+    }
+
+    pub fn keystream(&self) -> &[u8] {
+        &self.keystream
+    }
+
+    /// Score margin between the best and second best byte of each column. Bigger is more
+    /// confident, and bytes fixed by hand are infinitely so.
+    pub fn confidence(&self) -> &[f64] {
+        &self.confidence
+    }
+
+    /// Keystream positions ordered from least to most confident.
+    pub fn least_confident(&self) -> Vec<usize> {
+        let mut positions = (0..self.keystream.len()).collect::<Vec<usize>>();
+        positions.sort_by(|&a, &b| self.confidence[a].total_cmp(&self.confidence[b]));
+
+        positions
+    }
+
+    /// Every ciphertext decrypted as far as the keystream reaches.
+    pub fn plaintexts(&self) -> Vec<Vec<u8>> {
+        self.ciphertexts
+            .iter()
+            .map(|ciphertext| xor_bytes(ciphertext, &self.keystream))
+            .collect()
+    }
+
+    /// Fixes one keystream byte, growing the keystream with unknown zero bytes if needed.
+    pub fn set_keystream_byte(&mut self, position: usize, value: u8) {
+        if position >= self.keystream.len() {
+            self.keystream.resize(position + 1, 0);
+            self.confidence.resize(position + 1, 0.0);
+        }
+
+        self.keystream[position] = value;
+        self.confidence[position] = f64::INFINITY;
+    }
+
+    /// Fixes the keystream under `guess`, taken as the plaintext of ciphertext `index`
+    /// starting at `offset`.
+    pub fn guess_plaintext(
+        &mut self,
+        index: usize,
+        offset: usize,
+        guess: &[u8],
+    ) -> Result<(), CryptopalsError> {
+        let ciphertext = self
+            .ciphertexts
+            .get(index)
+            .ok_or(CryptopalsError::InvalidIndex(index))?;
+        if offset + guess.len() > ciphertext.len() {
+            return Err(CryptopalsError::InvalidDataLength(offset + guess.len()));
+        }
+
+        let keystream = xor_bytes(&ciphertext[offset..offset + guess.len()], guess);
+        for (i, byte) in keystream.into_iter().enumerate() {
+            self.set_keystream_byte(offset + i, byte);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis::UnigramChiSquared,
+        block::{encrypt_aes_ctr, CounterLayout},
+    };
+
+    use super::*;
+
+    const ENGLISH: &str = include_str!("../../resources/english.txt");
+
+    fn sentences() -> Vec<Vec<u8>> {
+        ENGLISH
+            .split(". ")
+            .map(|sentence| sentence.trim().as_bytes().to_vec())
+            .filter(|sentence| sentence.len() >= 40)
+            .collect()
+    }
+
+    fn encrypt_all(plaintexts: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        plaintexts
+            .iter()
+            .map(|plain| {
+                encrypt_aes_ctr(
+                    plain,
+                    b"YELLOW SUBMARINE",
+                    &[0; 8],
+                    CounterLayout::LittleEndian64,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fixed_nonce_ctr_truncated() {
+        let plaintexts = sentences();
+        let ciphertexts = encrypt_all(&plaintexts);
+        let shortest = plaintexts.iter().map(Vec::len).min().unwrap();
+
+        let mut attack = FixedNonceCtrBreak::new(
+            &ciphertexts,
+            &UnigramChiSquared::default(),
+            ColumnAlignment::Truncate,
+        )
+        .unwrap();
+        assert_eq!(shortest, attack.keystream().len());
+
+        let recovered = attack.plaintexts();
+        let total = shortest * plaintexts.len();
+        let correct = plaintexts
+            .iter()
+            .zip(&recovered)
+            .flat_map(|(plain, recovered)| plain.iter().zip(recovered))
+            .filter(|(a, b)| a == b)
+            .count();
+        assert!(correct * 10 >= total * 9, "{correct} of {total} bytes");
+
+        for position in attack.least_confident() {
+            let plain = &plaintexts[0][position..position + 1];
+            attack.guess_plaintext(0, position, plain).unwrap();
+        }
+        for (plain, recovered) in plaintexts.iter().zip(attack.plaintexts()) {
+            assert_eq!(plain[..shortest], recovered);
+        }
+        assert!(attack.confidence().iter().all(|c| c.is_infinite()));
+    }
+
+    #[test]
+    fn fixed_nonce_ctr_longest_and_refinement() {
+        let plaintexts = sentences();
+        let ciphertexts = encrypt_all(&plaintexts);
+        let longest = plaintexts.iter().map(Vec::len).max().unwrap();
+
+        let mut attack = FixedNonceCtrBreak::new(
+            &ciphertexts,
+            &UnigramChiSquared::default(),
+            ColumnAlignment::Longest,
+        )
+        .unwrap();
+        assert_eq!(longest, attack.keystream().len());
+
+        let (index, plain) = plaintexts
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, plain)| plain.len())
+            .unwrap();
+        attack.guess_plaintext(index, 0, plain).unwrap();
+        for (plain, recovered) in plaintexts.iter().zip(attack.plaintexts()) {
+            assert_eq!(*plain, recovered);
+        }
+
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(longest + 1)),
+            attack.guess_plaintext(index, 1, plain)
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidIndex(plaintexts.len())),
+            attack.guess_plaintext(plaintexts.len(), 0, b"a")
+        );
+        assert_eq!(
+            Some(CryptopalsError::InvalidDataLength(0)),
+            FixedNonceCtrBreak::new(
+                &[],
+                &UnigramChiSquared::default(),
+                ColumnAlignment::Truncate
+            )
+            .err()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use rand::RngCore;
+
+use crate::{
+    error::CryptopalsError,
+    random::{decrypt_mt19937, untemper, untemper_64, Mt19937, Mt19937_64},
+};
+
+/// Rebuilds a generator from 624 consecutive outputs. The clone continues right after the
+/// last output given.
+pub fn clone_mt19937(outputs: &[u32]) -> Result<Mt19937, CryptopalsError> {
+    if outputs.len() < Mt19937::STATE_SIZE {
+        return Err(CryptopalsError::InvalidDataLength(outputs.len()));
+    }
+
+    let mut state = [0; Mt19937::STATE_SIZE];
+    for (word, &output) in state.iter_mut().zip(outputs) {
+        *word = untemper(output);
+    }
+
+    let mut clone = Mt19937::from_state(state);
+    for _ in Mt19937::STATE_SIZE..outputs.len() {
+        clone.next_u32();
+    }
+
+    Ok(clone)
+}
+
+/// Rebuilds a 64-bit generator from 312 consecutive outputs.
+pub fn clone_mt19937_64(outputs: &[u64]) -> Result<Mt19937_64, CryptopalsError> {
+    if outputs.len() < Mt19937_64::STATE_SIZE {
+        return Err(CryptopalsError::InvalidDataLength(outputs.len()));
+    }
+
+    let mut state = [0; Mt19937_64::STATE_SIZE];
+    for (word, &output) in state.iter_mut().zip(outputs) {
+        *word = untemper_64(output);
+    }
+
+    let mut clone = Mt19937_64::from_state(state);
+    for _ in Mt19937_64::STATE_SIZE..outputs.len() {
+        clone.next_u64();
+    }
+
+    Ok(clone)
+}
+
+/// Finds the timestamp, at most `window` seconds before `now`, that seeded a generator whose
+/// first output is `output`.
+pub fn recover_time_seed(output: u32, now: u64, window: u64) -> Option<u32> {
+    (now.saturating_sub(window)..=now)
+        .rev()
+        .map(|timestamp| timestamp as u32)
+        .find(|&seed| Mt19937::new(seed).next_u32() == output)
+}
+
+/// Tries every 16-bit key until the ciphertext decrypts to something ending in
+/// `known_suffix`.
+pub fn recover_mt19937_key(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    if known_suffix.is_empty() || known_suffix.len() > ciphertext.len() {
+        return None;
+    }
+
+    (0..=u16::MAX).find(|&key| decrypt_mt19937(ciphertext, key).ends_with(known_suffix))
+}
+
+/// Tells whether `token` is the start of MT19937 output seeded with a timestamp at most
+/// `window` seconds before `now`, and if so returns that seed.
+pub fn detect_time_seeded_token(token: &[u8], now: u64, window: u64) -> Option<u32> {
+    if token.is_empty() {
+        return None;
+    }
+
+    let mut candidate = vec![0; token.len()];
+    (now.saturating_sub(window)..=now)
+        .rev()
+        .map(|timestamp| timestamp as u32)
+        .find(|&seed| {
+            Mt19937::new(seed).fill_bytes(&mut candidate);
+            candidate == token
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        oracles::{
+            mt19937_stream::prefix_oracle, password_reset::password_reset_token,
+            time_seeded::time_seeded_output,
+        },
+        random::{Clock, SeededRng, SimulatedClock},
+    };
+
+    use super::*;
+
+    #[test]
+    fn clone_mt19937_from_outputs() {
+        let mut rng = Mt19937::new(0xc0ffee);
+        let outputs = (0..700).map(|_| rng.next_u32()).collect::<Vec<u32>>();
+
+        let mut clone = clone_mt19937(&outputs).unwrap();
+        for _ in 0..2000 {
+            assert_eq!(rng.next_u32(), clone.next_u32());
+        }
+        assert_eq!(
+            Some(CryptopalsError::InvalidDataLength(623)),
+            clone_mt19937(&outputs[..623]).err()
+        );
+
+        let mut rng = Mt19937_64::new(0xc0ffee);
+        let outputs = (0..312).map(|_| rng.next_u64()).collect::<Vec<u64>>();
+        let mut clone = clone_mt19937_64(&outputs).unwrap();
+        for _ in 0..1000 {
+            assert_eq!(rng.next_u64(), clone.next_u64());
+        }
+    }
+
+    #[test]
+    fn recover_time_seed_works() {
+        let mut rng = SeededRng::from_env();
+        let clock = SimulatedClock::new(1_700_000_000);
+        let (seed, output) = time_seeded_output(&mut rng, &clock);
+
+        assert_eq!(Some(seed), recover_time_seed(output, clock.now(), 2000));
+    }
+
+    #[test]
+    fn recover_mt19937_key_from_suffix() {
+        let mut rng = SeededRng::from_env();
+        let (key, oracle) = prefix_oracle(&mut rng);
+        let known_suffix = b"AAAAAAAAAAAAAA";
+
+        let ciphertext = oracle(known_suffix);
+        assert_eq!(Some(key), recover_mt19937_key(&ciphertext, known_suffix));
+        assert_eq!(None, recover_mt19937_key(&ciphertext, b""));
+    }
+
+    #[test]
+    fn detect_time_seeded_token_works() {
+        let mut rng = SeededRng::from_env();
+        let clock = SimulatedClock::new(1_700_000_000);
+        let token = password_reset_token(&clock, 16);
+        clock.advance(600);
+
+        assert_eq!(
+            Some(1_700_000_000),
+            detect_time_seeded_token(&token, clock.now(), 3600)
+        );
+        assert_eq!(None, detect_time_seeded_token(&token, clock.now(), 300));
+
+        let mut random_token = [0; 16];
+        rng.fill_bytes(&mut random_token);
+        assert_eq!(
+            None,
+            detect_time_seeded_token(&random_token, clock.now(), 3600)
+        );
+        assert_eq!(None, detect_time_seeded_token(&[], clock.now(), 3600));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{error::CryptopalsError, oracles::EncryptionOracle};
+
+pub fn compute_block_size_and_padding_length<O>(
+    oracle: &O,
+) -> Result<(usize, usize), CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    const MAX_PROBE_LENGTH: usize = 256;
+
+    let mut previous_length = oracle.try_encrypt(&[])?.len();
+    for i in 1..=MAX_PROBE_LENGTH {
+        let current_length = oracle.try_encrypt(&vec![0; i])?.len();
+        if current_length < previous_length {
+            return Err(CryptopalsError::OracleFailure(
+                "Ciphertext shrunk when the input grew".to_string(),
+            ));
+        }
+
+        if current_length > previous_length {
+            return Ok((current_length - previous_length, i));
+        }
+        previous_length = current_length;
+    }
+
+    Err(CryptopalsError::OracleFailure(format!(
+        "Ciphertext length did not change within {MAX_PROBE_LENGTH} bytes"
+    )))
+}
+
+pub fn prefix_length<O>(oracle: &O, block_size: usize) -> Result<usize, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let ciphertext_a = oracle.try_encrypt(&[])?;
+    let ciphertext_b = oracle.try_encrypt(&[0])?;
+    let prefix_smaller_than_block_size = ciphertext_a[0..block_size] != ciphertext_b[0..block_size];
+    if prefix_smaller_than_block_size {
+        bytes_within_last_prefix_block(oracle, 0, block_size)
+    } else {
+        let blocks_in_prefix = full_blocks_within_prefix(&ciphertext_a, &ciphertext_b, block_size);
+
+        let initial_bytes = blocks_in_prefix * block_size;
+        let last_bytes = bytes_within_last_prefix_block(oracle, initial_bytes, block_size)?;
+        Ok(initial_bytes + last_bytes)
+    }
+}
+
+fn full_blocks_within_prefix(ciphertext_a: &[u8], ciphertext_b: &[u8], block_size: usize) -> usize {
+    ciphertext_a
+        .chunks(block_size)
+        .zip(ciphertext_b.chunks(block_size))
+        .take_while(|(block_a, block_b)| block_a == block_b)
+        .count()
+}
+
+fn bytes_within_last_prefix_block<O>(
+    oracle: &O,
+    block_position: usize,
+    block_size: usize,
+) -> Result<usize, CryptopalsError>
+where
+    O: EncryptionOracle + ?Sized,
+{
+    let start = block_position;
+    let end = block_position + block_size;
+    let ciphertext_block = |length: usize| -> Result<Vec<u8>, CryptopalsError> {
+        oracle
+            .try_encrypt(&vec![0; length])?
+            .get(start..end)
+            .map(<[u8]>::to_vec)
+            .ok_or(CryptopalsError::OracleFailure(
+                "Ciphertext ended inside the prefix".to_string(),
+            ))
+    };
+
+    for i in 0..=block_size {
+        if ciphertext_block(i)? == ciphertext_block(i + 1)? {
+            return match i < block_size {
+                true => Ok(block_size - i),
+                false => Ok(0),
+            };
+        }
+    }
+
+    Err(CryptopalsError::OracleFailure(
+        "Prefix changed between queries".to_string(),
+    ))
+}
@@ -0,0 +1,183 @@
+use base64::prelude::*;
+
+use crate::{
+    analysis::{hamming_distance_bytes, transpose, Scorer},
+    error::CryptopalsError,
+    xor::RepeatingKeyXor,
+};
+
+use super::break_single_xor_bytes;
+
+const MAX_KEYSIZE: usize = 40;
+const NUM_KEYSIZE_CANDIDATES: usize = 3;
+const MAX_COMPARED_BLOCKS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysizeCandidate {
+    pub keysize: usize,
+    pub normalized_distance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatingKeyDecryptMetadata {
+    pub key: Vec<u8>,
+    pub keysizes: Vec<KeysizeCandidate>,
+    pub column_scores: Vec<f64>,
+    pub decrypted_data: Vec<u8>,
+}
+
+pub fn break_repeating_key_xor_hex(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<RepeatingKeyDecryptMetadata, CryptopalsError> {
+    let data = hex::decode(data)?;
+    break_repeating_key_xor_bytes(&data, scorer)
+}
+
+pub fn break_repeating_key_xor_base64(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<RepeatingKeyDecryptMetadata, CryptopalsError> {
+    let data = BASE64_STANDARD.decode(data)?;
+    break_repeating_key_xor_bytes(&data, scorer)
+}
+
+/// Breaks the ciphertext with each of the most likely keysizes and keeps the best plaintext.
+pub fn break_repeating_key_xor_bytes(
+    data: &[u8],
+    scorer: &dyn Scorer,
+) -> Result<RepeatingKeyDecryptMetadata, CryptopalsError> {
+    let keysizes = rank_keysizes(data)
+        .into_iter()
+        .take(NUM_KEYSIZE_CANDIDATES)
+        .collect::<Vec<KeysizeCandidate>>();
+
+    let mut best: Option<(f64, RepeatingKeyDecryptMetadata)> = None;
+    for candidate in &keysizes {
+        let data_chunks = data.chunks(candidate.keysize).collect::<Vec<&[u8]>>();
+        let columns = transpose(&data_chunks)
+            .iter()
+            .map(|column| break_single_xor_bytes(column, scorer))
+            .collect::<Result<Vec<_>, CryptopalsError>>()?;
+
+        let key = columns.iter().map(|column| column.key).collect::<Vec<u8>>();
+        let decrypted_data = RepeatingKeyXor::new(&key)?.apply(data);
+
+        let score = scorer.score(&decrypted_data);
+        if best
+            .as_ref()
+            .is_none_or(|(best_score, _)| score < *best_score)
+        {
+            let metadata = RepeatingKeyDecryptMetadata {
+                key,
+                keysizes: vec![],
+                column_scores: columns.iter().map(|column| column.score).collect(),
+                decrypted_data,
+            };
+            best = Some((score, metadata));
+        }
+    }
+
+    let (_, metadata) = best.ok_or(CryptopalsError::InvalidDataLength(data.len()))?;
+
+    Ok(RepeatingKeyDecryptMetadata {
+        keysizes,
+        ..metadata
+    })
+}
+
+/// Every keysize that fits at least two blocks in the data, lowest normalized distance first.
+pub fn rank_keysizes(data: &[u8]) -> Vec<KeysizeCandidate> {
+    let mut keysizes = (2..=MAX_KEYSIZE)
+        .filter_map(|keysize| {
+            let distance = avg_hamming_distance_bytes(data, keysize)?;
+            Some(KeysizeCandidate {
+                keysize,
+                normalized_distance: distance / keysize as f64,
+            })
+        })
+        .collect::<Vec<KeysizeCandidate>>();
+    keysizes.sort_by(|a, b| a.normalized_distance.total_cmp(&b.normalized_distance));
+
+    keysizes
+}
+
+/// Average distance between every pair of the first `MAX_COMPARED_BLOCKS` blocks, or `None`
+/// when fewer than two blocks fit.
+fn avg_hamming_distance_bytes(data: &[u8], keysize: usize) -> Option<f64> {
+    let blocks = data
+        .chunks_exact(keysize)
+        .take(MAX_COMPARED_BLOCKS)
+        .collect::<Vec<&[u8]>>();
+    if blocks.len() < 2 {
+        return None;
+    }
+
+    let num_pairs = blocks.len() * (blocks.len() - 1) / 2;
+    let sum_distances = (0..blocks.len())
+        .flat_map(|i| (i + 1..blocks.len()).map(move |j| (i, j)))
+        .map(|(i, j)| hamming_distance_bytes(blocks[i], blocks[j]))
+        .sum::<u64>();
+
+    Some(sum_distances as f64 / num_pairs as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::UnigramChiSquared;
+
+    use super::*;
+
+    #[test]
+    fn break_repeating_key_xor_short_data() {
+        let scorer = UnigramChiSquared::default();
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(0)),
+            break_repeating_key_xor_bytes(&[], &scorer)
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(3)),
+            break_repeating_key_xor_bytes(&[1, 2, 3], &scorer)
+        );
+        assert!(break_repeating_key_xor_bytes(&[1, 2, 3, 4, 5], &scorer).is_ok());
+    }
+
+    #[test]
+    fn break_repeating_key_xor_works() {
+        let plain = b"A short key word decided which rule to use at each position. If the length \
+of the key word can be found, the message can be split into columns, and each column is nothing \
+more than a simple substitution that can be solved by looking at the frequency of its letters.";
+        let key = b"SECRET";
+        let data = RepeatingKeyXor::new(key).unwrap().apply(plain);
+
+        let keysizes = rank_keysizes(&data);
+        assert_eq!(MAX_KEYSIZE - 1, keysizes.len());
+        assert!(keysizes
+            .windows(2)
+            .all(|pair| pair[0].normalized_distance <= pair[1].normalized_distance));
+
+        let metadata = break_repeating_key_xor_bytes(&data, &UnigramChiSquared::default()).unwrap();
+        assert_eq!(key.to_vec(), metadata.key);
+        assert_eq!(plain.to_vec(), metadata.decrypted_data);
+        assert_eq!(key.len(), metadata.column_scores.len());
+        assert_eq!(NUM_KEYSIZE_CANDIDATES, metadata.keysizes.len());
+    }
+
+    #[test]
+    fn rank_keysizes_bounds_work() {
+        assert_eq!(None, avg_hamming_distance_bytes(&[1, 2, 3], 4));
+        assert_eq!(None, avg_hamming_distance_bytes(&[1, 2, 3], 2));
+        assert!(rank_keysizes(&[1, 2, 3]).is_empty());
+        let mut keysizes = rank_keysizes(&[0; 7])
+            .iter()
+            .map(|candidate| candidate.keysize)
+            .collect::<Vec<usize>>();
+        keysizes.sort();
+        assert_eq!(vec![2, 3], keysizes);
+
+        let data = RepeatingKeyXor::new(b"KEY")
+            .unwrap()
+            .apply(&vec![b'e'; 1 << 20]);
+        assert_eq!(MAX_KEYSIZE - 1, rank_keysizes(&data).len());
+    }
+}
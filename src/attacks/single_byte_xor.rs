@@ -0,0 +1,178 @@
+use base64::prelude::*;
+
+use crate::{analysis::Scorer, error::CryptopalsError, xor::xor_bytes};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecryptMetadata {
+    pub key: u8,
+    pub score: f64,
+    pub decrypted_data: Vec<u8>,
+}
+
+/// Every single-byte key, best score first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RankedCandidates {
+    pub candidates: Vec<DecryptMetadata>,
+}
+
+impl RankedCandidates {
+    pub fn best(&self) -> Option<&DecryptMetadata> {
+        self.candidates.first()
+    }
+
+    pub fn top(&self, n: usize) -> &[DecryptMetadata] {
+        &self.candidates[..n.min(self.candidates.len())]
+    }
+
+    /// Score difference between the first and second candidates. Bigger is more confident.
+    pub fn confidence_margin(&self) -> Option<f64> {
+        match self.candidates.as_slice() {
+            [first, second, ..] => Some(second.score - first.score),
+            _ => None,
+        }
+    }
+}
+
+pub fn rank_single_xor_hex(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<RankedCandidates, CryptopalsError> {
+    let data = hex::decode(data)?;
+    Ok(rank_single_xor_bytes(&data, scorer))
+}
+
+pub fn rank_single_xor_base64(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<RankedCandidates, CryptopalsError> {
+    let data = BASE64_STANDARD.decode(data)?;
+    Ok(rank_single_xor_bytes(&data, scorer))
+}
+
+pub fn rank_single_xor_bytes(data: &[u8], scorer: &dyn Scorer) -> RankedCandidates {
+    if data.is_empty() {
+        return RankedCandidates::default();
+    }
+
+    let mut candidates = (0..=255u8)
+        .map(|key| {
+            let decrypted_data = xor_bytes(data, &vec![key; data.len()]);
+            DecryptMetadata {
+                key,
+                score: scorer.score(&decrypted_data),
+                decrypted_data,
+            }
+        })
+        .collect::<Vec<DecryptMetadata>>();
+    candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    RankedCandidates { candidates }
+}
+
+pub fn break_single_xor_hex(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<DecryptMetadata, CryptopalsError> {
+    let data = hex::decode(data)?;
+    break_single_xor_bytes(&data, scorer)
+}
+
+pub fn break_single_xor_base64(
+    data: &str,
+    scorer: &dyn Scorer,
+) -> Result<DecryptMetadata, CryptopalsError> {
+    let data = BASE64_STANDARD.decode(data)?;
+    break_single_xor_bytes(&data, scorer)
+}
+
+pub fn break_single_xor_bytes(
+    data: &[u8],
+    scorer: &dyn Scorer,
+) -> Result<DecryptMetadata, CryptopalsError> {
+    rank_single_xor_bytes(data, scorer)
+        .candidates
+        .into_iter()
+        .next()
+        .ok_or(CryptopalsError::InvalidDataLength(data.len()))
+}
+
+pub fn detect_single_xor_ciphertext_hex(
+    sentences: &[&str],
+    scorer: &dyn Scorer,
+) -> Result<Option<DecryptMetadata>, CryptopalsError> {
+    detect_single_xor_ciphertext(sentences, scorer, break_single_xor_hex)
+}
+
+pub fn detect_single_xor_ciphertext_base64(
+    sentences: &[&str],
+    scorer: &dyn Scorer,
+) -> Result<Option<DecryptMetadata>, CryptopalsError> {
+    detect_single_xor_ciphertext(sentences, scorer, break_single_xor_base64)
+}
+
+fn detect_single_xor_ciphertext(
+    sentences: &[&str],
+    scorer: &dyn Scorer,
+    bruteforce_fn: fn(&str, &dyn Scorer) -> Result<DecryptMetadata, CryptopalsError>,
+) -> Result<Option<DecryptMetadata>, CryptopalsError> {
+    let decryped_data = sentences
+        .iter()
+        .filter(|sentence| !sentence.is_empty())
+        .map(|sentence| bruteforce_fn(sentence, scorer))
+        .collect::<Result<Vec<DecryptMetadata>, CryptopalsError>>()?
+        .into_iter()
+        .min_by(|a, b| {
+            a.score
+                .partial_cmp(&b.score)
+                .unwrap_or(std::cmp::Ordering::Greater)
+        });
+
+    Ok(decryped_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::UnigramChiSquared;
+
+    use super::*;
+
+    #[test]
+    fn rank_single_xor_works() {
+        let encrypted_data = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
+        let ranking = rank_single_xor_hex(encrypted_data, &UnigramChiSquared::default()).unwrap();
+
+        assert_eq!(256, ranking.candidates.len());
+        assert_eq!(b'X', ranking.best().unwrap().key);
+        assert_eq!(3, ranking.top(3).len());
+        assert!(ranking
+            .candidates
+            .windows(2)
+            .all(|pair| pair[0].score <= pair[1].score));
+        assert!(ranking.confidence_margin().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn break_single_xor_binary_data() {
+        let plain = "Ünïcödé in Latin-1 is not valid UTF-8 at all"
+            .chars()
+            .map(|c| c as u8)
+            .collect::<Vec<u8>>();
+        let encrypted_data = xor_bytes(&plain, &vec![0xa7; plain.len()]);
+
+        let metadata =
+            break_single_xor_bytes(&encrypted_data, &UnigramChiSquared::default()).unwrap();
+        assert_eq!(0xa7, metadata.key);
+        assert_eq!(plain, metadata.decrypted_data);
+    }
+
+    #[test]
+    fn rank_single_xor_empty_data() {
+        let ranking = rank_single_xor_bytes(&[], &UnigramChiSquared::default());
+        assert!(ranking.best().is_none());
+        assert!(ranking.confidence_margin().is_none());
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(0)),
+            break_single_xor_bytes(&[], &UnigramChiSquared::default())
+        );
+    }
+}
@@ -0,0 +1,98 @@
+mod cbc;
+mod cfb;
+mod cipher;
+mod ctr;
+mod ecb;
+mod gcm;
+mod ofb;
+mod pcbc;
+
+use crate::{error::CryptopalsError, padding::Padding};
+
+pub use cbc::{
+    decrypt_aes_cbc, decrypt_cbc, decrypt_cbc_with_padding, encrypt_aes_cbc, encrypt_cbc,
+    encrypt_cbc_with_padding,
+};
+pub use cfb::{
+    decrypt_aes_cfb, decrypt_aes_cfb8, decrypt_cfb, decrypt_cfb8, encrypt_aes_cfb,
+    encrypt_aes_cfb8, encrypt_cfb, encrypt_cfb8,
+};
+pub use cipher::{Aes, BlockCipher};
+pub use ctr::{decrypt_aes_ctr, encrypt_aes_ctr, CounterLayout, Ctr, CtrReader, CtrWriter};
+pub use ecb::{
+    decrypt_aes_ecb, decrypt_ecb, decrypt_ecb_with_padding, encrypt_aes_ecb, encrypt_ecb,
+    encrypt_ecb_with_padding,
+};
+pub use gcm::{gf128_multiply, ghash, AesGcm};
+pub use ofb::{decrypt_aes_ofb, decrypt_ofb, encrypt_aes_ofb, encrypt_ofb};
+pub use pcbc::{
+    decrypt_aes_pcbc, decrypt_pcbc, decrypt_pcbc_with_padding, encrypt_aes_pcbc, encrypt_pcbc,
+    encrypt_pcbc_with_padding,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockMode {
+    ECB,
+    CBC,
+    PCBC,
+    CFB,
+    CFB8,
+    OFB,
+}
+
+impl BlockMode {
+    pub const ALL: [BlockMode; 6] = [
+        BlockMode::ECB,
+        BlockMode::CBC,
+        BlockMode::PCBC,
+        BlockMode::CFB,
+        BlockMode::CFB8,
+        BlockMode::OFB,
+    ];
+}
+
+/// Encrypts with AES in the given mode. ECB ignores the IV.
+pub fn encrypt_aes(
+    mode: BlockMode,
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    match mode {
+        BlockMode::ECB => encrypt_aes_ecb(data, key),
+        BlockMode::CBC => encrypt_aes_cbc(data, key, iv),
+        BlockMode::PCBC => encrypt_aes_pcbc(data, key, iv),
+        BlockMode::CFB => encrypt_aes_cfb(data, key, iv),
+        BlockMode::CFB8 => encrypt_aes_cfb8(data, key, iv),
+        BlockMode::OFB => encrypt_aes_ofb(data, key, iv),
+    }
+}
+
+fn valid_iv<C: BlockCipher>(cipher: &C, iv: Option<Vec<u8>>) -> Result<Vec<u8>, CryptopalsError> {
+    let iv = iv.unwrap_or(vec![0; cipher.block_size()]);
+    check_iv_length(cipher, &iv)?;
+
+    Ok(iv)
+}
+
+fn check_iv_length<C: BlockCipher>(cipher: &C, iv: &[u8]) -> Result<(), CryptopalsError> {
+    if iv.len() != cipher.block_size() {
+        return Err(CryptopalsError::InvalidIvLength(iv.len()));
+    }
+
+    Ok(())
+}
+
+/// Pads `data` and checks the scheme left whole blocks, which `NoPadding` may not.
+fn pad_blocks<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let padded = padding.pad(data, cipher.block_size())?;
+    if !padded.len().is_multiple_of(cipher.block_size()) {
+        return Err(CryptopalsError::InvalidDataLength(padded.len()));
+    }
+
+    Ok(padded)
+}
@@ -0,0 +1,170 @@
+use crate::{
+    error::CryptopalsError,
+    padding::{Padding, Pkcs7},
+    xor::xor_into,
+};
+
+use super::{check_iv_length, pad_blocks, valid_iv, Aes, BlockCipher};
+
+pub fn encrypt_aes_cbc(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    encrypt_cbc(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn decrypt_aes_cbc(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    decrypt_cbc(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn encrypt_cbc<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_cbc_with_padding(cipher, data, iv, &Pkcs7)
+}
+
+pub fn decrypt_cbc<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    decrypt_cbc_with_padding(cipher, data, iv, &Pkcs7)
+}
+
+pub fn encrypt_cbc_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut ciphertext = pad_blocks(cipher, data, padding)?;
+    let mut prev_chunk = iv.to_vec();
+    for curr_chunk in ciphertext.chunks_mut(cipher.block_size()) {
+        xor_into(curr_chunk, &prev_chunk);
+        cipher.encrypt_block(curr_chunk);
+
+        prev_chunk.copy_from_slice(curr_chunk);
+    }
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_cbc_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+    if data.is_empty() || !data.len().is_multiple_of(cipher.block_size()) {
+        return Err(CryptopalsError::InvalidDataLength(data.len()));
+    }
+
+    let mut plain = data.to_vec();
+    let prev_chunks = [iv].into_iter().chain(data.chunks(cipher.block_size()));
+    for (curr_chunk, prev_chunk) in plain.chunks_mut(cipher.block_size()).zip(prev_chunks) {
+        cipher.decrypt_block(curr_chunk);
+        xor_into(curr_chunk, prev_chunk);
+    }
+
+    padding.unpad(&plain, cipher.block_size())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        padding::{AnsiX923, Iso10126, Iso7816, NoPadding},
+        random::SeededRng,
+    };
+
+    use super::*;
+
+    /// Rotates each byte of an 8-byte block and xors it with the key. Only good for tests.
+    struct ToyCipher(u8);
+
+    impl BlockCipher for ToyCipher {
+        fn block_size(&self) -> usize {
+            8
+        }
+
+        fn encrypt_block(&self, block: &mut [u8]) {
+            block.rotate_left(1);
+            block.iter_mut().for_each(|byte| *byte ^= self.0);
+        }
+
+        fn decrypt_block(&self, block: &mut [u8]) {
+            block.iter_mut().for_each(|byte| *byte ^= self.0);
+            block.rotate_right(1);
+        }
+    }
+
+    #[test]
+    fn cbc_works_with_any_block_cipher() {
+        let cipher = ToyCipher(0x5a);
+        let message = b"Repeated block, repeated block, repeated block.";
+        let iv = [1; 8];
+
+        let ciphertext = encrypt_cbc(&cipher, message, &iv).unwrap();
+        assert_eq!(48, ciphertext.len());
+        assert_ne!(ciphertext[..8], ciphertext[16..24]);
+        assert_eq!(
+            message.to_vec(),
+            decrypt_cbc(&cipher, &ciphertext, &iv).unwrap()
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidIvLength(16)),
+            encrypt_cbc(&cipher, message, &[0; 16])
+        );
+    }
+
+    #[test]
+    fn aes_cbc_accepts_every_key_size() {
+        for key_length in [16, 24, 32] {
+            let key = vec![3; key_length];
+            let ciphertext = encrypt_aes_cbc(b"any key size", &key, None).unwrap();
+            assert_eq!(
+                b"any key size".to_vec(),
+                decrypt_aes_cbc(&ciphertext, &key, None).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn cbc_with_other_padding_schemes() {
+        let cipher = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        let message = b"Padding is chosen per mode.";
+        let iso10126 = Iso10126::new(SeededRng::from_env());
+        let schemes: [&dyn Padding; 4] = [&Pkcs7, &AnsiX923, &iso10126, &Iso7816];
+        for padding in schemes {
+            let ciphertext = encrypt_cbc_with_padding(&cipher, message, &[0; 16], padding).unwrap();
+            assert_eq!(32, ciphertext.len());
+            assert_eq!(
+                message.to_vec(),
+                decrypt_cbc_with_padding(&cipher, &ciphertext, &[0; 16], padding).unwrap()
+            );
+        }
+
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(27)),
+            encrypt_cbc_with_padding(&cipher, message, &[0; 16], &NoPadding)
+        );
+        let ciphertext =
+            encrypt_cbc_with_padding(&cipher, b"YELLOW SUBMARINE", &[0; 16], &NoPadding).unwrap();
+        assert_eq!(16, ciphertext.len());
+        assert_eq!(
+            b"YELLOW SUBMARINE".to_vec(),
+            decrypt_cbc_with_padding(&cipher, &ciphertext, &[0; 16], &NoPadding).unwrap()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{error::CryptopalsError, xor::xor_into};
+
+use super::{check_iv_length, valid_iv, Aes, BlockCipher};
+
+pub fn encrypt_aes_cfb(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    encrypt_cfb(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn decrypt_aes_cfb(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    decrypt_cfb(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn encrypt_aes_cfb8(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    encrypt_cfb8(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn decrypt_aes_cfb8(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    decrypt_cfb8(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+/// Full-block CFB. The last block may be partial, so there is no padding.
+pub fn encrypt_cfb<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut ciphertext = data.to_vec();
+    let mut register = iv.to_vec();
+    for chunk in ciphertext.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(&mut register);
+        xor_into(chunk, &register[..chunk.len()]);
+        register[..chunk.len()].copy_from_slice(chunk);
+    }
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_cfb<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut plain = data.to_vec();
+    let mut register = iv.to_vec();
+    for chunk in plain.chunks_mut(cipher.block_size()) {
+        let mut keystream = register.clone();
+        cipher.encrypt_block(&mut keystream);
+        register[..chunk.len()].copy_from_slice(chunk);
+        xor_into(chunk, &keystream[..chunk.len()]);
+    }
+
+    Ok(plain)
+}
+
+/// CFB with 8-bit feedback: one block encryption per byte.
+pub fn encrypt_cfb8<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut ciphertext = data.to_vec();
+    let mut register = iv.to_vec();
+    for byte in ciphertext.iter_mut() {
+        let mut keystream = register.clone();
+        cipher.encrypt_block(&mut keystream);
+        *byte ^= keystream[0];
+
+        register.rotate_left(1);
+        *register.last_mut().expect("IV is not empty") = *byte;
+    }
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_cfb8<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut plain = data.to_vec();
+    let mut register = iv.to_vec();
+    for byte in plain.iter_mut() {
+        let mut keystream = register.clone();
+        cipher.encrypt_block(&mut keystream);
+
+        register.rotate_left(1);
+        *register.last_mut().expect("IV is not empty") = *byte;
+        *byte ^= keystream[0];
+    }
+
+    Ok(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NIST_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const NIST_IV: &str = "000102030405060708090a0b0c0d0e0f";
+    const NIST_PLAIN: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51";
+
+    #[test]
+    fn cfb_nist_vectors() {
+        let key = hex::decode(NIST_KEY).unwrap();
+        let iv = Some(hex::decode(NIST_IV).unwrap());
+        let plain = hex::decode(NIST_PLAIN).unwrap();
+
+        let ciphertext = encrypt_aes_cfb(&plain, &key, iv.clone()).unwrap();
+        assert_eq!(
+            "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b",
+            hex::encode(&ciphertext)
+        );
+        assert_eq!(
+            plain,
+            decrypt_aes_cfb(&ciphertext, &key, iv.clone()).unwrap()
+        );
+
+        let ciphertext = encrypt_aes_cfb8(&plain[..18], &key, iv.clone()).unwrap();
+        assert_eq!(
+            "3b79424c9c0dd436bace9e0ed4586a4f32b9",
+            hex::encode(&ciphertext)
+        );
+        assert_eq!(
+            plain[..18],
+            decrypt_aes_cfb8(&ciphertext, &key, iv).unwrap()
+        );
+    }
+
+    #[test]
+    fn cfb_partial_last_block() {
+        let key = b"YELLOW SUBMARINE";
+        let ciphertext = encrypt_aes_cfb(b"no padding needed", key, None).unwrap();
+        assert_eq!(17, ciphertext.len());
+        assert_eq!(
+            b"no padding needed".to_vec(),
+            decrypt_aes_cfb(&ciphertext, key, None).unwrap()
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidIvLength(8)),
+            encrypt_aes_cfb8(b"data", key, Some(vec![0; 8]))
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use aes::{
+    cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, KeyInit},
+    Aes128, Aes192, Aes256,
+};
+
+use crate::error::CryptopalsError;
+
+/// A keyed permutation over fixed-size blocks. Blocks passed in must be `block_size` bytes.
+pub trait BlockCipher {
+    fn block_size(&self) -> usize;
+    fn encrypt_block(&self, block: &mut [u8]);
+    fn decrypt_block(&self, block: &mut [u8]);
+}
+
+/// AES with the variant picked from the key length.
+#[derive(Clone)]
+pub enum Aes {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl Aes {
+    pub fn new(key: &[u8]) -> Result<Self, CryptopalsError> {
+        let cipher = match key.len() {
+            16 => Self::Aes128(Aes128::new_from_slice(key).expect("Key is 16 bytes")),
+            24 => Self::Aes192(Aes192::new_from_slice(key).expect("Key is 24 bytes")),
+            32 => Self::Aes256(Aes256::new_from_slice(key).expect("Key is 32 bytes")),
+            length => return Err(CryptopalsError::InvalidKeyLength(length)),
+        };
+
+        Ok(cipher)
+    }
+}
+
+impl BlockCipher for Aes {
+    fn block_size(&self) -> usize {
+        16
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.encrypt_block(block),
+            Self::Aes192(cipher) => cipher.encrypt_block(block),
+            Self::Aes256(cipher) => cipher.encrypt_block(block),
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.decrypt_block(block),
+            Self::Aes192(cipher) => cipher.decrypt_block(block),
+            Self::Aes256(cipher) => cipher.decrypt_block(block),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aes_key_sizes() {
+        for key_length in [16, 24, 32] {
+            let cipher = Aes::new(&vec![7; key_length]).unwrap();
+            let mut block = *b"YELLOW SUBMARINE";
+            cipher.encrypt_block(&mut block);
+            assert_ne!(*b"YELLOW SUBMARINE", block);
+            cipher.decrypt_block(&mut block);
+            assert_eq!(*b"YELLOW SUBMARINE", block);
+        }
+
+        assert_eq!(
+            Some(CryptopalsError::InvalidKeyLength(20)),
+            Aes::new(&[0; 20]).err()
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use std::io::{self, Read, Write};
+
+use crate::{error::CryptopalsError, xor::xor_into};
+
+use super::{Aes, BlockCipher};
+
+/// How the nonce and the block counter share a counter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterLayout {
+    /// Nonce followed by a 64-bit little-endian counter, as in the Cryptopals challenges.
+    LittleEndian64,
+    /// Nonce followed by a 64-bit big-endian counter.
+    BigEndian64,
+    /// Nonce followed by a 32-bit big-endian counter, as in GCM.
+    BigEndian32,
+}
+
+impl CounterLayout {
+    fn counter_length(&self) -> usize {
+        match self {
+            Self::LittleEndian64 | Self::BigEndian64 => 8,
+            Self::BigEndian32 => 4,
+        }
+    }
+
+    fn encode(&self, counter: u64) -> Vec<u8> {
+        match self {
+            Self::LittleEndian64 => counter.to_le_bytes().to_vec(),
+            Self::BigEndian64 => counter.to_be_bytes().to_vec(),
+            Self::BigEndian32 => (counter as u32).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// CTR keystream over any block cipher. Encryption and decryption are the same operation.
+#[derive(Clone)]
+pub struct Ctr<C> {
+    cipher: C,
+    nonce: Vec<u8>,
+    layout: CounterLayout,
+    initial_counter: u64,
+    position: u64,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    /// The nonce fills whatever part of the block the counter does not.
+    pub fn new(cipher: C, nonce: &[u8], layout: CounterLayout) -> Result<Self, CryptopalsError> {
+        if nonce.len() + layout.counter_length() != cipher.block_size() {
+            return Err(CryptopalsError::InvalidIvLength(nonce.len()));
+        }
+
+        Ok(Self {
+            cipher,
+            nonce: nonce.to_vec(),
+            layout,
+            initial_counter: 0,
+            position: 0,
+        })
+    }
+
+    pub fn with_initial_counter(mut self, counter: u64) -> Self {
+        self.initial_counter = counter;
+        self
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Moves to a byte offset in the keystream.
+    pub fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    pub fn keystream_block(&self, block_index: u64) -> Vec<u8> {
+        let counter = self.initial_counter.wrapping_add(block_index);
+        let mut block = [self.nonce.as_slice(), &self.layout.encode(counter)].concat();
+        self.cipher.encrypt_block(&mut block);
+
+        block
+    }
+
+    /// Xors `data` with the keystream starting at `position`, without moving.
+    pub fn apply_keystream_at(&self, data: &mut [u8], position: u64) {
+        let block_size = self.cipher.block_size() as u64;
+
+        let mut position = position;
+        let mut data = data;
+        while !data.is_empty() {
+            let offset = (position % block_size) as usize;
+            let keystream = self.keystream_block(position / block_size);
+            let length = data.len().min(keystream.len() - offset);
+
+            let (chunk, rest) = data.split_at_mut(length);
+            xor_into(chunk, &keystream[offset..offset + length]);
+            position += length as u64;
+            data = rest;
+        }
+    }
+
+    /// Xors `data` with the keystream at the current position and moves past it.
+    pub fn apply_keystream(&mut self, data: &mut [u8]) {
+        self.apply_keystream_at(data, self.position);
+        self.position += data.len() as u64;
+    }
+
+    pub fn reader<R: Read>(self, inner: R) -> CtrReader<C, R> {
+        CtrReader { ctr: self, inner }
+    }
+
+    pub fn writer<W: Write>(self, inner: W) -> CtrWriter<C, W> {
+        CtrWriter { ctr: self, inner }
+    }
+}
+
+pub fn encrypt_aes_ctr(
+    data: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+    layout: CounterLayout,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let mut ctr = Ctr::new(Aes::new(key)?, nonce, layout)?;
+    let mut ciphertext = data.to_vec();
+    ctr.apply_keystream(&mut ciphertext);
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_aes_ctr(
+    data: &[u8],
+    key: &[u8],
+    nonce: &[u8],
+    layout: CounterLayout,
+) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_aes_ctr(data, key, nonce, layout)
+}
+
+/// Applies the keystream to everything read from the inner reader.
+pub struct CtrReader<C, R> {
+    ctr: Ctr<C>,
+    inner: R,
+}
+
+impl<C, R> CtrReader<C, R> {
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<C: BlockCipher, R: Read> Read for CtrReader<C, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let num_bytes = self.inner.read(buf)?;
+        self.ctr.apply_keystream(&mut buf[..num_bytes]);
+
+        Ok(num_bytes)
+    }
+}
+
+/// Applies the keystream to everything written before passing it to the inner writer.
+pub struct CtrWriter<C, W> {
+    ctr: Ctr<C>,
+    inner: W,
+}
+
+impl<C, W> CtrWriter<C, W> {
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<C: BlockCipher, W: Write> Write for CtrWriter<C, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut data = buf.to_vec();
+        self.ctr.apply_keystream_at(&mut data, self.ctr.position);
+        let num_bytes = self.inner.write(&data)?;
+        self.ctr.position += num_bytes as u64;
+
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::prelude::*;
+
+    use super::*;
+
+    const NIST_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const NIST_PLAIN: &str = "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710";
+    const NIST_CIPHERTEXT: &str = "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee";
+
+    #[test]
+    fn ctr_cryptopals_layout() {
+        let ciphertext = BASE64_STANDARD
+            .decode("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==")
+            .unwrap();
+        let plain = decrypt_aes_ctr(
+            &ciphertext,
+            b"YELLOW SUBMARINE",
+            &[0; 8],
+            CounterLayout::LittleEndian64,
+        )
+        .unwrap();
+        assert_eq!(
+            b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ".to_vec(),
+            plain
+        );
+    }
+
+    #[test]
+    fn ctr_nist_big_endian_layouts() {
+        let key = hex::decode(NIST_KEY).unwrap();
+        let plain = hex::decode(NIST_PLAIN).unwrap();
+        let initial_block = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+
+        let layouts = [
+            (CounterLayout::BigEndian64, 8, 0xf8f9fafbfcfdfeff),
+            (CounterLayout::BigEndian32, 12, 0xfcfdfeff),
+        ];
+        for (layout, nonce_length, counter) in layouts {
+            let ctr = Ctr::new(
+                Aes::new(&key).unwrap(),
+                &initial_block[..nonce_length],
+                layout,
+            )
+            .unwrap()
+            .with_initial_counter(counter);
+
+            let mut data = plain.clone();
+            ctr.apply_keystream_at(&mut data, 0);
+            assert_eq!(NIST_CIPHERTEXT, hex::encode(&data));
+        }
+    }
+
+    #[test]
+    fn ctr_seeks_and_streams() {
+        let key = b"YELLOW SUBMARINE";
+        let plain = b"Counter mode lets readers start anywhere in the stream.";
+        let ciphertext =
+            encrypt_aes_ctr(plain, key, &[7; 8], CounterLayout::LittleEndian64).unwrap();
+
+        let mut ctr = Ctr::new(
+            Aes::new(key).unwrap(),
+            &[7; 8],
+            CounterLayout::LittleEndian64,
+        )
+        .unwrap();
+        ctr.seek(21);
+        let mut middle = ciphertext[21..30].to_vec();
+        ctr.apply_keystream(&mut middle);
+        assert_eq!(plain[21..30], middle);
+        assert_eq!(30, ctr.position());
+
+        ctr.seek(0);
+        let mut writer = ctr.clone().writer(vec![]);
+        for chunk in plain.chunks(5) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(ciphertext, writer.into_inner());
+
+        let mut decrypted = vec![];
+        ctr.reader(ciphertext.as_slice())
+            .read_to_end(&mut decrypted)
+            .unwrap();
+        assert_eq!(plain.to_vec(), decrypted);
+
+        assert_eq!(
+            Some(CryptopalsError::InvalidIvLength(12)),
+            Ctr::new(Aes::new(key).unwrap(), &[0; 12], CounterLayout::BigEndian64).err()
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    error::CryptopalsError,
+    padding::{Padding, Pkcs7},
+};
+
+use super::{pad_blocks, Aes, BlockCipher};
+
+pub fn encrypt_aes_ecb(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    Ok(encrypt_ecb(&Aes::new(key)?, data))
+}
+
+pub fn decrypt_aes_ecb(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    decrypt_ecb(&Aes::new(key)?, data)
+}
+
+pub fn encrypt_ecb<C: BlockCipher>(cipher: &C, data: &[u8]) -> Vec<u8> {
+    encrypt_ecb_with_padding(cipher, data, &Pkcs7).expect("PKCS#7 fills the last block")
+}
+
+pub fn decrypt_ecb<C: BlockCipher>(cipher: &C, data: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    decrypt_ecb_with_padding(cipher, data, &Pkcs7)
+}
+
+pub fn encrypt_ecb_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let mut ciphertext = pad_blocks(cipher, data, padding)?;
+    for block in ciphertext.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(block);
+    }
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_ecb_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    if data.is_empty() || !data.len().is_multiple_of(cipher.block_size()) {
+        return Err(CryptopalsError::InvalidDataLength(data.len()));
+    }
+
+    let mut plain = data.to_vec();
+    for block in plain.chunks_mut(cipher.block_size()) {
+        cipher.decrypt_block(block);
+    }
+
+    padding.unpad(&plain, cipher.block_size())
+}
@@ -0,0 +1,228 @@
+use crate::{error::CryptopalsError, xor::xor_into};
+
+use super::{Aes, BlockCipher, CounterLayout, Ctr};
+
+const MIN_TAG_LENGTH: usize = 4;
+const MAX_TAG_LENGTH: usize = 16;
+
+/// AES in Galois/Counter Mode.
+#[derive(Clone)]
+pub struct AesGcm {
+    cipher: Aes,
+    hash_key: u128,
+    tag_length: usize,
+}
+
+impl AesGcm {
+    pub fn new(key: &[u8]) -> Result<Self, CryptopalsError> {
+        let cipher = Aes::new(key)?;
+        let mut hash_key = [0; 16];
+        cipher.encrypt_block(&mut hash_key);
+
+        Ok(Self {
+            cipher,
+            hash_key: u128::from_be_bytes(hash_key),
+            tag_length: MAX_TAG_LENGTH,
+        })
+    }
+
+    /// Truncates tags to `tag_length` bytes, between 4 and 16.
+    pub fn with_tag_length(mut self, tag_length: usize) -> Result<Self, CryptopalsError> {
+        if !(MIN_TAG_LENGTH..=MAX_TAG_LENGTH).contains(&tag_length) {
+            return Err(CryptopalsError::InvalidTagLength(tag_length));
+        }
+
+        self.tag_length = tag_length;
+        Ok(self)
+    }
+
+    pub fn hash_key(&self) -> u128 {
+        self.hash_key
+    }
+
+    /// Returns the ciphertext and the tag.
+    pub fn encrypt(
+        &self,
+        iv: &[u8],
+        aad: &[u8],
+        plain: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>), CryptopalsError> {
+        let counter_block = self.counter_block(iv)?;
+
+        let mut ciphertext = plain.to_vec();
+        self.keystream(counter_block)
+            .apply_keystream(&mut ciphertext);
+        let tag = self.tag(counter_block, aad, &ciphertext);
+
+        Ok((ciphertext, tag))
+    }
+
+    pub fn decrypt(
+        &self,
+        iv: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> Result<Vec<u8>, CryptopalsError> {
+        if tag.len() != self.tag_length {
+            return Err(CryptopalsError::InvalidTagLength(tag.len()));
+        }
+
+        let counter_block = self.counter_block(iv)?;
+        let expected_tag = self.tag(counter_block, aad, ciphertext);
+        let difference = expected_tag
+            .iter()
+            .zip(tag)
+            .fold(0, |difference, (a, b)| difference | (a ^ b));
+        if difference != 0 {
+            return Err(CryptopalsError::InvalidTag);
+        }
+
+        let mut plain = ciphertext.to_vec();
+        self.keystream(counter_block).apply_keystream(&mut plain);
+
+        Ok(plain)
+    }
+
+    /// The pre-counter block J0: 96-bit IVs get a counter of 1 appended, others are hashed.
+    fn counter_block(&self, iv: &[u8]) -> Result<u128, CryptopalsError> {
+        match iv.len() {
+            0 => Err(CryptopalsError::InvalidIvLength(0)),
+            12 => Ok(u128::from_be_bytes(
+                [iv, &[0, 0, 0, 1]]
+                    .concat()
+                    .try_into()
+                    .expect("Block is 16 bytes"),
+            )),
+            _ => Ok(ghash(self.hash_key, &[], iv)),
+        }
+    }
+
+    fn keystream(&self, counter_block: u128) -> Ctr<Aes> {
+        let counter_block = counter_block.to_be_bytes();
+        let counter =
+            u32::from_be_bytes(counter_block[12..].try_into().expect("Counter is 4 bytes"));
+
+        Ctr::new(
+            self.cipher.clone(),
+            &counter_block[..12],
+            CounterLayout::BigEndian32,
+        )
+        .expect("Nonce is 12 bytes")
+        .with_initial_counter(counter as u64 + 1)
+    }
+
+    fn tag(&self, counter_block: u128, aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+        let mut tag = ghash(self.hash_key, aad, ciphertext).to_be_bytes();
+        let mut mask = counter_block.to_be_bytes();
+        self.cipher.encrypt_block(&mut mask);
+        xor_into(&mut tag, &mask);
+
+        tag[..self.tag_length].to_vec()
+    }
+}
+
+/// Multiplication in GF(2^128) with GCM's reflected bit order.
+pub fn gf128_multiply(x: u128, y: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+
+    let mut product = 0;
+    let mut v = y;
+    for i in (0..128).rev() {
+        if (x >> i) & 1 == 1 {
+            product ^= v;
+        }
+        v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+    }
+
+    product
+}
+
+/// GHASH of the zero-padded AAD and ciphertext followed by their bit lengths.
+pub fn ghash(hash_key: u128, aad: &[u8], ciphertext: &[u8]) -> u128 {
+    let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+
+    aad.chunks(16)
+        .chain(ciphertext.chunks(16))
+        .map(|chunk| {
+            let mut block = [0; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            u128::from_be_bytes(block)
+        })
+        .chain([lengths])
+        .fold(0, |hash, block| gf128_multiply(hash ^ block, hash_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::read_resource;
+
+    use super::*;
+
+    fn test_vectors() -> Vec<HashMap<String, String>> {
+        let data = read_resource("gcm", "gcm_test_vectors.rsp");
+
+        let mut vectors = vec![];
+        for group in data.split("\n\n") {
+            let vector = group
+                .lines()
+                .filter(|line| !line.starts_with('#') && !line.starts_with('['))
+                .map(|line| match line.split_once(" = ") {
+                    Some((name, value)) => (name.to_string(), value.to_string()),
+                    None => (line.trim_end_matches(" =").to_string(), String::new()),
+                })
+                .collect::<HashMap<String, String>>();
+            if vector.contains_key("Count") {
+                vectors.push(vector);
+            }
+        }
+
+        vectors
+    }
+
+    #[test]
+    fn gcm_nist_vectors() {
+        let vectors = test_vectors();
+        assert_eq!(22, vectors.len());
+
+        for vector in vectors {
+            let field = |name: &str| hex::decode(&vector[name]).unwrap();
+            let gcm = AesGcm::new(&field("Key"))
+                .unwrap()
+                .with_tag_length(field("Tag").len())
+                .unwrap();
+
+            let decrypted = gcm.decrypt(&field("IV"), &field("AAD"), &field("CT"), &field("Tag"));
+            if vector.contains_key("FAIL") {
+                assert_eq!(Err(CryptopalsError::InvalidTag), decrypted);
+                continue;
+            }
+            assert_eq!(field("PT"), decrypted.unwrap());
+
+            let (ciphertext, tag) = gcm
+                .encrypt(&field("IV"), &field("AAD"), &field("PT"))
+                .unwrap();
+            assert_eq!(field("CT"), ciphertext);
+            assert_eq!(field("Tag"), tag);
+        }
+    }
+
+    #[test]
+    fn gcm_rejects_invalid_parameters() {
+        let gcm = AesGcm::new(b"YELLOW SUBMARINE").unwrap();
+        assert_eq!(
+            Some(CryptopalsError::InvalidTagLength(3)),
+            gcm.clone().with_tag_length(3).err()
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidIvLength(0)),
+            gcm.encrypt(&[], &[], b"data")
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidTagLength(12)),
+            gcm.decrypt(&[0; 12], &[], b"data", &[0; 12])
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{error::CryptopalsError, xor::xor_into};
+
+use super::{check_iv_length, valid_iv, Aes, BlockCipher};
+
+pub fn encrypt_aes_ofb(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    encrypt_ofb(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn decrypt_aes_ofb(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_aes_ofb(data, key, iv)
+}
+
+/// OFB keystream applied to the data. Encryption and decryption are the same operation.
+pub fn encrypt_ofb<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut output = data.to_vec();
+    let mut register = iv.to_vec();
+    for chunk in output.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(&mut register);
+        xor_into(chunk, &register[..chunk.len()]);
+    }
+
+    Ok(output)
+}
+
+pub fn decrypt_ofb<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_ofb(cipher, data, iv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ofb_nist_vector() {
+        let key = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let iv = Some(hex::decode("000102030405060708090a0b0c0d0e0f").unwrap());
+        let plain = hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51")
+            .unwrap();
+
+        let ciphertext = encrypt_aes_ofb(&plain, &key, iv.clone()).unwrap();
+        assert_eq!(
+            "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825",
+            hex::encode(&ciphertext)
+        );
+        assert_eq!(plain, decrypt_aes_ofb(&ciphertext, &key, iv).unwrap());
+    }
+}
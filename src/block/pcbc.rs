@@ -0,0 +1,116 @@
+use crate::{
+    error::CryptopalsError,
+    padding::{Padding, Pkcs7},
+    xor::xor_into,
+};
+
+use super::{check_iv_length, pad_blocks, valid_iv, Aes, BlockCipher};
+
+pub fn encrypt_aes_pcbc(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    encrypt_pcbc(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+pub fn decrypt_aes_pcbc(
+    data: &[u8],
+    key: &[u8],
+    iv: Option<Vec<u8>>,
+) -> Result<Vec<u8>, CryptopalsError> {
+    let cipher = Aes::new(key)?;
+    decrypt_pcbc(&cipher, data, &valid_iv(&cipher, iv)?)
+}
+
+/// Like CBC, but each block is chained with both the previous plaintext and ciphertext.
+pub fn encrypt_pcbc<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_pcbc_with_padding(cipher, data, iv, &Pkcs7)
+}
+
+pub fn decrypt_pcbc<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    decrypt_pcbc_with_padding(cipher, data, iv, &Pkcs7)
+}
+
+pub fn encrypt_pcbc_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+
+    let mut ciphertext = pad_blocks(cipher, data, padding)?;
+    let mut chain = iv.to_vec();
+    for block in ciphertext.chunks_mut(cipher.block_size()) {
+        let plain_block = block.to_vec();
+        xor_into(block, &chain);
+        cipher.encrypt_block(block);
+
+        chain = plain_block;
+        xor_into(&mut chain, block);
+    }
+
+    Ok(ciphertext)
+}
+
+pub fn decrypt_pcbc_with_padding<C: BlockCipher>(
+    cipher: &C,
+    data: &[u8],
+    iv: &[u8],
+    padding: &dyn Padding,
+) -> Result<Vec<u8>, CryptopalsError> {
+    check_iv_length(cipher, iv)?;
+    if data.is_empty() || !data.len().is_multiple_of(cipher.block_size()) {
+        return Err(CryptopalsError::InvalidDataLength(data.len()));
+    }
+
+    let mut plain = data.to_vec();
+    let mut chain = iv.to_vec();
+    for block in plain.chunks_mut(cipher.block_size()) {
+        let ciphertext_block = block.to_vec();
+        cipher.decrypt_block(block);
+        xor_into(block, &chain);
+
+        chain = ciphertext_block;
+        xor_into(&mut chain, block);
+    }
+
+    padding.unpad(&plain, cipher.block_size())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcbc_roundtrip() {
+        let key = b"YELLOW SUBMARINE";
+        let iv = Some(vec![9; 16]);
+        let message = b"Propagating cipher block chaining spreads errors further.";
+
+        let ciphertext = encrypt_aes_pcbc(message, key, iv.clone()).unwrap();
+        assert_eq!(64, ciphertext.len());
+        assert_ne!(
+            ciphertext,
+            crate::block::encrypt_cbc(&Aes::new(key).unwrap(), message, &[9; 16]).unwrap()
+        );
+        assert_eq!(
+            message.to_vec(),
+            decrypt_aes_pcbc(&ciphertext, key, iv).unwrap()
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(15)),
+            decrypt_aes_pcbc(&[0; 15], key, None)
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use base64::prelude::*;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use cryptopals::{
+    analysis::{
+        max_repeated_block, FrequencyTable, NgramLogLikelihood, PrintableAscii, Scorer,
+        UnigramChiSquared,
+    },
+    attacks::{break_repeating_key_xor_bytes, break_single_xor_bytes},
+    block::{
+        decrypt_aes_cbc, decrypt_aes_ecb, encrypt_aes_cbc, encrypt_aes_ctr, encrypt_aes_ecb,
+        CounterLayout,
+    },
+    padding::{AnsiX923, Iso10126, Iso7816, NoPadding, Padding, Pkcs7, ZeroPadding},
+    xor::RepeatingKeyXor,
+};
+use rand::rngs::OsRng;
+
+#[derive(Parser)]
+#[command(
+    name = "cryptopals",
+    about = "Tools built on the cryptopals challenges"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert data between encodings
+    Encode(IoArgs),
+    /// XOR data against a (repeating) key
+    Xor {
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        key: KeyArgs,
+    },
+    /// Recover the key of single-byte or repeating-key XOR ciphertext
+    BreakXor {
+        #[command(flatten)]
+        io: IoArgs,
+        /// Kind of XOR used to produce the ciphertext
+        #[arg(long, value_enum, default_value_t = XorKind::Repeating)]
+        kind: XorKind,
+        #[command(flatten)]
+        scorer: ScorerArgs,
+    },
+    /// Find the line most likely encrypted with AES in ECB mode
+    DetectEcb(IoArgs),
+    /// AES in ECB mode, with a 16, 24 or 32 byte key
+    AesEcb {
+        #[arg(value_enum)]
+        direction: Direction,
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        key: KeyArgs,
+    },
+    /// AES in CBC mode, with a 16, 24 or 32 byte key
+    AesCbc {
+        #[arg(value_enum)]
+        direction: Direction,
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        key: KeyArgs,
+        /// Initialization vector, encoded as --key-format (defaults to all zeros)
+        #[arg(long)]
+        iv: Option<String>,
+    },
+    /// AES in CTR mode. Encryption and decryption are the same operation
+    AesCtr {
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        key: KeyArgs,
+        /// Nonce, encoded as --key-format (defaults to all zeros)
+        #[arg(long)]
+        nonce: Option<String>,
+        #[arg(long, value_enum, default_value_t = Layout::LittleEndian64)]
+        layout: Layout,
+    },
+    /// Apply padding, PKCS#7 by default
+    Pad {
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        padding: PaddingArgs,
+    },
+    /// Strip and validate padding, PKCS#7 by default
+    Unpad {
+        #[command(flatten)]
+        io: IoArgs,
+        #[command(flatten)]
+        padding: PaddingArgs,
+    },
+}
+
+#[derive(Args)]
+struct IoArgs {
+    /// File to read, stdin when missing
+    input: Option<PathBuf>,
+    /// File to write, stdout when missing
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Encoding::Raw)]
+    input_format: Encoding,
+    #[arg(long, value_enum, default_value_t = Encoding::Hex)]
+    output_format: Encoding,
+}
+
+#[derive(Args)]
+struct PaddingArgs {
+    #[arg(long, default_value_t = 16)]
+    block_size: u8,
+    #[arg(long, value_enum, default_value_t = Scheme::Pkcs7)]
+    scheme: Scheme,
+}
+
+#[derive(Args)]
+struct KeyArgs {
+    #[arg(short, long)]
+    key: String,
+    #[arg(long, value_enum, default_value_t = Encoding::Raw)]
+    key_format: Encoding,
+}
+
+#[derive(Args)]
+struct ScorerArgs {
+    /// How candidate plaintexts are ranked
+    #[arg(long, value_enum, default_value_t = ScorerKind::ChiSquared)]
+    scorer: ScorerKind,
+    /// Sample plaintext to train the scorer on instead of the built-in English model
+    #[arg(long)]
+    corpus: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ScorerKind {
+    ChiSquared,
+    Bigram,
+    Trigram,
+    Printable,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Encoding {
+    Hex,
+    Base64,
+    Raw,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Layout {
+    LittleEndian64,
+    BigEndian64,
+    BigEndian32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Scheme {
+    Pkcs7,
+    AnsiX923,
+    Iso10126,
+    Iso7816,
+    Zero,
+    None,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum XorKind {
+    Single,
+    Repeating,
+}
+
+pub fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Encode(io) => {
+            let data = read_input(&io)?;
+            write_output(&io, &data)
+        }
+        Command::Xor { io, key } => {
+            let data = read_input(&io)?;
+            let cipher = RepeatingKeyXor::new(&key.decode()?)?;
+            write_output(&io, &cipher.apply(&data))
+        }
+        Command::BreakXor { io, kind, scorer } => {
+            let data = read_input(&io)?;
+            let scorer = scorer.build()?;
+            let key = match kind {
+                XorKind::Single => vec![break_single_xor_bytes(&data, scorer.as_ref())?.key],
+                XorKind::Repeating => break_repeating_key_xor_bytes(&data, scorer.as_ref())?.key,
+            };
+            eprintln!(
+                "key: {} ({})",
+                hex::encode(&key),
+                String::from_utf8_lossy(&key)
+            );
+
+            write_output(&io, &RepeatingKeyXor::new(&key)?.apply(&data))
+        }
+        Command::DetectEcb(io) => {
+            let raw = read_raw(&io)?;
+            let lines = String::from_utf8(raw)?
+                .lines()
+                .map(|line| decode(line.as_bytes(), io.input_format))
+                .collect::<Result<Vec<Vec<u8>>, Box<dyn Error>>>()?;
+
+            let (index, line) = lines
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, line)| max_repeated_block(line))
+                .ok_or("Input has no lines")?;
+            eprintln!("line: {}", index + 1);
+            write_output(&io, line)
+        }
+        Command::AesEcb { direction, io, key } => {
+            let data = read_input(&io)?;
+            let key = key.decode()?;
+            let result = match direction {
+                Direction::Encrypt => encrypt_aes_ecb(&data, &key)?,
+                Direction::Decrypt => decrypt_aes_ecb(&data, &key)?,
+            };
+            write_output(&io, &result)
+        }
+        Command::AesCbc {
+            direction,
+            io,
+            key,
+            iv,
+        } => {
+            let data = read_input(&io)?;
+            let iv = iv
+                .map(|iv| decode(iv.as_bytes(), key.key_format))
+                .transpose()?;
+            let key = key.decode()?;
+            let result = match direction {
+                Direction::Encrypt => encrypt_aes_cbc(&data, &key, iv)?,
+                Direction::Decrypt => decrypt_aes_cbc(&data, &key, iv)?,
+            };
+            write_output(&io, &result)
+        }
+        Command::AesCtr {
+            io,
+            key,
+            nonce,
+            layout,
+        } => {
+            let data = read_input(&io)?;
+            let layout = match layout {
+                Layout::LittleEndian64 => CounterLayout::LittleEndian64,
+                Layout::BigEndian64 => CounterLayout::BigEndian64,
+                Layout::BigEndian32 => CounterLayout::BigEndian32,
+            };
+            let nonce = match nonce {
+                Some(nonce) => decode(nonce.as_bytes(), key.key_format)?,
+                None if layout == CounterLayout::BigEndian32 => vec![0; 12],
+                None => vec![0; 8],
+            };
+            let key = key.decode()?;
+            write_output(&io, &encrypt_aes_ctr(&data, &key, &nonce, layout)?)
+        }
+        Command::Pad { io, padding } => {
+            let (scheme, block_size) = padding.build()?;
+            let data = read_input(&io)?;
+            write_output(&io, &scheme.pad(&data, block_size)?)
+        }
+        Command::Unpad { io, padding } => {
+            let (scheme, block_size) = padding.build()?;
+            let data = read_input(&io)?;
+            write_output(&io, &scheme.unpad(&data, block_size)?)
+        }
+    }
+}
+
+impl PaddingArgs {
+    fn build(&self) -> Result<(Box<dyn Padding>, usize), Box<dyn Error>> {
+        if self.block_size == 0 {
+            return Err("Block size must be greater than zero".into());
+        }
+
+        let scheme: Box<dyn Padding> = match self.scheme {
+            Scheme::Pkcs7 => Box::new(Pkcs7),
+            Scheme::AnsiX923 => Box::new(AnsiX923),
+            Scheme::Iso10126 => Box::new(Iso10126::new(OsRng)),
+            Scheme::Iso7816 => Box::new(Iso7816),
+            Scheme::Zero => Box::new(ZeroPadding),
+            Scheme::None => Box::new(NoPadding),
+        };
+
+        Ok((scheme, self.block_size as usize))
+    }
+}
+
+impl KeyArgs {
+    fn decode(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        decode(self.key.as_bytes(), self.key_format)
+    }
+}
+
+impl ScorerArgs {
+    fn build(&self) -> Result<Box<dyn Scorer>, Box<dyn Error>> {
+        let corpus = self.corpus.as_ref().map(std::fs::read).transpose()?;
+        let scorer: Box<dyn Scorer> = match (self.scorer, corpus) {
+            (ScorerKind::ChiSquared, None) => Box::new(UnigramChiSquared::default()),
+            (ScorerKind::ChiSquared, Some(corpus)) => {
+                Box::new(UnigramChiSquared::new(FrequencyTable::train(&corpus)))
+            }
+            (ScorerKind::Bigram, None) => Box::new(NgramLogLikelihood::english_bigrams()),
+            (ScorerKind::Bigram, Some(corpus)) => Box::new(NgramLogLikelihood::bigrams(&corpus)),
+            (ScorerKind::Trigram, None) => Box::new(NgramLogLikelihood::english_trigrams()),
+            (ScorerKind::Trigram, Some(corpus)) => Box::new(NgramLogLikelihood::trigrams(&corpus)),
+            (ScorerKind::Printable, _) => Box::new(PrintableAscii),
+        };
+
+        Ok(scorer)
+    }
+}
+
+fn read_raw(io: &IoArgs) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut data = vec![];
+    match &io.input {
+        Some(path) => File::open(path)?.read_to_end(&mut data)?,
+        None => io::stdin().read_to_end(&mut data)?,
+    };
+
+    Ok(data)
+}
+
+fn read_input(io: &IoArgs) -> Result<Vec<u8>, Box<dyn Error>> {
+    decode(&read_raw(io)?, io.input_format)
+}
+
+fn write_output(io: &IoArgs, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    let encoded = encode(data, io.output_format);
+    match &io.output {
+        Some(path) => File::create(path)?.write_all(&encoded)?,
+        None => io::stdout().write_all(&encoded)?,
+    };
+
+    Ok(())
+}
+
+fn decode(data: &[u8], encoding: Encoding) -> Result<Vec<u8>, Box<dyn Error>> {
+    let text = || {
+        data.iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .copied()
+            .collect::<Vec<u8>>()
+    };
+
+    let decoded = match encoding {
+        Encoding::Hex => hex::decode(text())?,
+        Encoding::Base64 => BASE64_STANDARD.decode(text())?,
+        Encoding::Raw => data.to_vec(),
+    };
+
+    Ok(decoded)
+}
+
+fn encode(data: &[u8], encoding: Encoding) -> Vec<u8> {
+    match encoding {
+        Encoding::Hex => format!("{}\n", hex::encode(data)).into_bytes(),
+        Encoding::Base64 => format!("{}\n", BASE64_STANDARD.encode(data)).into_bytes(),
+        Encoding::Raw => data.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_ignores_whitespace() {
+        assert_eq!(
+            b"YELLOW SUBMARINE".to_vec(),
+            decode(b"WUVMTE9X\nIFNVQk1B\nUklORQ==\n", Encoding::Base64).unwrap()
+        );
+        assert_eq!(
+            vec![0xde, 0xad, 0xbe, 0xef],
+            decode(b"dead beef\n", Encoding::Hex).unwrap()
+        );
+        assert_eq!(b"a b\n".to_vec(), decode(b"a b\n", Encoding::Raw).unwrap());
+    }
+
+    #[test]
+    fn encode_terminates_text_encodings() {
+        assert_eq!(b"6869\n".to_vec(), encode(b"hi", Encoding::Hex));
+        assert_eq!(b"aGk=\n".to_vec(), encode(b"hi", Encoding::Base64));
+        assert_eq!(b"hi".to_vec(), encode(b"hi", Encoding::Raw));
+    }
+
+    #[test]
+    fn aes_ecb_roundtrips_binary_data() {
+        let dir = std::env::temp_dir().join(format!("cryptopals-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plain = dir.join("plain");
+        let encrypted = dir.join("encrypted");
+        let decrypted = dir.join("decrypted");
+        let data = vec![0xff, 0xfe, 0x00, 0x80, 0xc3, 0x28];
+        std::fs::write(&plain, &data).unwrap();
+
+        for (direction, input, output) in [
+            ("encrypt", &plain, &encrypted),
+            ("decrypt", &encrypted, &decrypted),
+        ] {
+            let cli = Cli::parse_from([
+                "cryptopals".as_ref(),
+                "aes-ecb".as_ref(),
+                direction.as_ref(),
+                input.as_os_str(),
+                "--output".as_ref(),
+                output.as_os_str(),
+                "--output-format".as_ref(),
+                "raw".as_ref(),
+                "--key".as_ref(),
+                "YELLOW SUBMARINE".as_ref(),
+            ]);
+            run(cli).unwrap();
+        }
+
+        assert_eq!(data, std::fs::read(&decrypted).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,8 @@
+use base64::prelude::*;
+
+use crate::error::CryptopalsError;
+
+pub fn hex_to_base64(data: &str) -> Result<String, CryptopalsError> {
+    let bytes = hex::decode(data)?;
+    Ok(BASE64_STANDARD.encode(bytes))
+}
@@ -0,0 +1,84 @@
+use std::string::FromUtf8Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CryptopalsError {
+    InvalidHex(hex::FromHexError),
+    InvalidBase64(base64::DecodeError),
+    InvalidKeyLength(usize),
+    InvalidIvLength(usize),
+    InvalidDataLength(usize),
+    InvalidIndex(usize),
+    MismatchedLengths(usize, usize),
+    InvalidPadding,
+    InvalidBlockSize(usize),
+    InvalidTagLength(usize),
+    InvalidTag,
+    InvalidUtf8(FromUtf8Error),
+    InvalidQueryString(String),
+    OracleFailure(String),
+    QueryBudgetExceeded(usize),
+    PartialRecovery(Vec<u8>, Box<CryptopalsError>),
+}
+
+impl std::fmt::Display for CryptopalsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidHex(error) => write!(f, "Invalid hex data: {error}"),
+            Self::InvalidBase64(error) => write!(f, "Invalid base64 data: {error}"),
+            Self::InvalidKeyLength(length) => write!(f, "Invalid key length: {length} bytes"),
+            Self::InvalidIvLength(length) => write!(f, "Invalid IV length: {length} bytes"),
+            Self::InvalidDataLength(length) => write!(f, "Invalid data length: {length} bytes"),
+            Self::InvalidIndex(index) => write!(f, "Invalid index: {index}"),
+            Self::MismatchedLengths(left, right) => {
+                write!(f, "Mismatched lengths: {left} and {right} bytes")
+            }
+            Self::InvalidPadding => write!(f, "Invalid padding"),
+            Self::InvalidBlockSize(size) => write!(f, "Invalid block size: {size} bytes"),
+            Self::InvalidTagLength(length) => write!(f, "Invalid tag length: {length} bytes"),
+            Self::InvalidTag => write!(f, "Authentication tag mismatch"),
+            Self::InvalidUtf8(error) => write!(f, "Plaintext is not valid UTF-8: {error}"),
+            Self::InvalidQueryString(query) => write!(f, "Invalid query string: {query}"),
+            Self::OracleFailure(reason) => write!(f, "Oracle failure: {reason}"),
+            Self::QueryBudgetExceeded(budget) => {
+                write!(f, "Oracle query budget of {budget} exceeded")
+            }
+            Self::PartialRecovery(recovered, error) => {
+                write!(
+                    f,
+                    "Recovered {} bytes before failing: {error}",
+                    recovered.len()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CryptopalsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidHex(error) => Some(error),
+            Self::InvalidBase64(error) => Some(error),
+            Self::InvalidUtf8(error) => Some(error),
+            Self::PartialRecovery(_, error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<hex::FromHexError> for CryptopalsError {
+    fn from(error: hex::FromHexError) -> Self {
+        Self::InvalidHex(error)
+    }
+}
+
+impl From<base64::DecodeError> for CryptopalsError {
+    fn from(error: base64::DecodeError) -> Self {
+        Self::InvalidBase64(error)
+    }
+}
+
+impl From<FromUtf8Error> for CryptopalsError {
+    fn from(error: FromUtf8Error) -> Self {
+        Self::InvalidUtf8(error)
+    }
+}
@@ -0,0 +1,25 @@
+pub mod analysis;
+pub mod attacks;
+pub mod block;
+pub mod encoding;
+pub mod error;
+pub mod oracles;
+pub mod padding;
+pub mod random;
+pub mod xor;
+
+pub mod set1;
+pub mod set2;
+pub mod set3;
+
+#[cfg(test)]
+fn read_resource(folder: &str, filename: &str) -> String {
+    use std::{fs::File, io::Read};
+
+    let mut file = File::open(format!("resources/{folder}/{filename}")).expect("File should exist");
+    let mut data = String::new();
+    file.read_to_string(&mut data)
+        .expect("File should contain valid data");
+
+    data
+}
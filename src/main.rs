@@ -1,16 +1,15 @@
-pub mod set1;
-pub mod set2;
+mod cli;
 
-#[cfg(test)]
-fn read_resource(folder: &str, filename: &str) -> String {
-    use std::{fs::File, io::Read};
+use std::process::ExitCode;
 
-    let mut file = File::open(format!("resources/{folder}/{filename}")).expect("File should exist");
-    let mut data = String::new();
-    file.read_to_string(&mut data)
-        .expect("File should contain valid data");
+use clap::Parser;
 
-    data
+fn main() -> ExitCode {
+    match cli::run(cli::Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
 }
-
-fn main() {}
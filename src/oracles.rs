@@ -0,0 +1,51 @@
+use rand::RngCore;
+
+use crate::error::CryptopalsError;
+
+pub mod ecb_prefix_suffix;
+pub mod ecb_suffix;
+pub mod instrumented;
+pub mod mt19937_stream;
+pub mod padding;
+pub mod password_reset;
+pub mod profile;
+pub mod random_mode;
+#[cfg(test)]
+pub(crate) mod testing;
+pub mod time_seeded;
+pub mod user_data;
+
+/// Encrypts attacker-chosen data under secrets the attacker does not know.
+pub trait EncryptionOracle {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8>;
+
+    /// Like `encrypt`, but lets the oracle refuse to answer. Attacks query through this.
+    fn try_encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+        Ok(self.encrypt(data))
+    }
+}
+
+/// Decrypts attacker-supplied ciphertexts and reveals only `Output` about the plaintext.
+pub trait DecryptionOracle {
+    type Output;
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Self::Output, CryptopalsError>;
+}
+
+impl<F> EncryptionOracle for F
+where
+    F: Fn(&[u8]) -> Vec<u8>,
+{
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        self(data)
+    }
+}
+
+pub const UNKNOWN_STRING: &str = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
+
+pub fn random_bytes<R: RngCore>(rng: &mut R, num_bytes: usize) -> Vec<u8> {
+    let mut bytes = vec![0; num_bytes];
+    rng.fill_bytes(&mut bytes);
+
+    bytes
+}
@@ -0,0 +1,55 @@
+use base64::prelude::*;
+use rand::{Rng, RngCore};
+
+use crate::{block::encrypt_aes_ecb, error::CryptopalsError};
+
+use super::{random_bytes, EncryptionOracle, UNKNOWN_STRING};
+
+/// Between 0 and 255 random bytes to hide in front of the attacker's input.
+pub fn random_prefix<R: RngCore>(rng: &mut R) -> Vec<u8> {
+    let length = rng.gen::<u8>() as usize;
+    random_bytes(rng, length)
+}
+
+pub fn encrypt_with_prefix(
+    prefix: &[u8],
+    data: &[u8],
+    key: &[u8],
+) -> Result<Vec<u8>, CryptopalsError> {
+    let unknown_string = BASE64_STANDARD.decode(UNKNOWN_STRING)?;
+    let data = [prefix, data, &unknown_string].concat();
+
+    encrypt_aes_ecb(&data, key)
+}
+
+/// Surrounds the input with a secret prefix and suffix and encrypts it with AES-128-ECB
+/// under its own key.
+#[derive(Debug, Clone)]
+pub struct EcbPrefixSuffixOracle {
+    key: Vec<u8>,
+    prefix: Vec<u8>,
+    suffix: Vec<u8>,
+}
+
+impl EcbPrefixSuffixOracle {
+    /// Uses a `random_prefix`.
+    pub fn new<R: RngCore>(rng: &mut R, suffix: &[u8]) -> Self {
+        let prefix = random_prefix(rng);
+        Self::with_prefix(rng, &prefix, suffix)
+    }
+
+    pub fn with_prefix<R: RngCore>(rng: &mut R, prefix: &[u8], suffix: &[u8]) -> Self {
+        Self {
+            key: random_bytes(rng, 16),
+            prefix: prefix.to_vec(),
+            suffix: suffix.to_vec(),
+        }
+    }
+}
+
+impl EncryptionOracle for EcbPrefixSuffixOracle {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        let data = [&self.prefix, data, &self.suffix].concat();
+        encrypt_aes_ecb(&data, &self.key).expect("Key is 16 bytes")
+    }
+}
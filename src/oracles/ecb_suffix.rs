@@ -0,0 +1,35 @@
+use base64::prelude::*;
+use rand::RngCore;
+
+use crate::{block::encrypt_aes_ecb, error::CryptopalsError};
+
+use super::{random_bytes, EncryptionOracle, UNKNOWN_STRING};
+
+pub fn encrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    let unknown_string = BASE64_STANDARD.decode(UNKNOWN_STRING)?;
+    let data = [data, &unknown_string].concat();
+
+    encrypt_aes_ecb(&data, key)
+}
+
+/// Appends a secret suffix to the input and encrypts it with AES-128-ECB under its own key.
+#[derive(Debug, Clone)]
+pub struct EcbSuffixOracle {
+    key: Vec<u8>,
+    suffix: Vec<u8>,
+}
+
+impl EcbSuffixOracle {
+    pub fn new<R: RngCore>(rng: &mut R, suffix: &[u8]) -> Self {
+        Self {
+            key: random_bytes(rng, 16),
+            suffix: suffix.to_vec(),
+        }
+    }
+}
+
+impl EncryptionOracle for EcbSuffixOracle {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        encrypt_aes_ecb(&[data, &self.suffix].concat(), &self.key).expect("Key is 16 bytes")
+    }
+}
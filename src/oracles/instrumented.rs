@@ -0,0 +1,175 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::error::CryptopalsError;
+
+use super::{DecryptionOracle, EncryptionOracle};
+
+/// What an `InstrumentedOracle` has answered so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OracleStats {
+    pub queries: usize,
+    pub bytes_sent: usize,
+    pub bytes_received: usize,
+}
+
+impl fmt::Display for OracleStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} queries, {} bytes sent, {} bytes received",
+            self.queries, self.bytes_sent, self.bytes_received
+        )
+    }
+}
+
+/// Wraps an oracle to count the queries and bytes that go through it, optionally refusing
+/// queries once a budget is spent.
+///
+/// `try_encrypt` and `decrypt` fail with `QueryBudgetExceeded` past the budget, which the
+/// attacks pass on. Calling `encrypt` directly past the budget panics.
+#[derive(Debug, Default)]
+pub struct InstrumentedOracle<O> {
+    inner: O,
+    budget: Option<usize>,
+    queries: AtomicUsize,
+    bytes_sent: AtomicUsize,
+    bytes_received: AtomicUsize,
+}
+
+impl<O> InstrumentedOracle<O> {
+    pub fn new(inner: O) -> Self {
+        Self {
+            inner,
+            budget: None,
+            queries: AtomicUsize::new(0),
+            bytes_sent: AtomicUsize::new(0),
+            bytes_received: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_budget(mut self, queries: usize) -> Self {
+        self.budget = Some(queries);
+        self
+    }
+
+    pub fn stats(&self) -> OracleStats {
+        OracleStats {
+            queries: self.queries.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.queries.store(0, Ordering::Relaxed);
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        self.bytes_received.store(0, Ordering::Relaxed);
+    }
+
+    pub fn into_inner(self) -> O {
+        self.inner
+    }
+
+    /// Takes one query from the budget. Refused queries are not counted.
+    fn record_query(&self, bytes_sent: usize) -> Result<(), CryptopalsError> {
+        let queries = self.queries.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(budget) = self.budget.filter(|&budget| queries > budget) {
+            self.queries.fetch_sub(1, Ordering::Relaxed);
+            return Err(CryptopalsError::QueryBudgetExceeded(budget));
+        }
+
+        self.bytes_sent.fetch_add(bytes_sent, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn record_response(&self, bytes_received: usize) {
+        self.bytes_received
+            .fetch_add(bytes_received, Ordering::Relaxed);
+    }
+}
+
+impl<O: EncryptionOracle> EncryptionOracle for InstrumentedOracle<O> {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        self.try_encrypt(data)
+            .expect("Oracle query budget exceeded")
+    }
+
+    fn try_encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+        self.record_query(data.len())?;
+        let ciphertext = self.inner.try_encrypt(data)?;
+        self.record_response(ciphertext.len());
+
+        Ok(ciphertext)
+    }
+}
+
+impl<O: DecryptionOracle> DecryptionOracle for InstrumentedOracle<O> {
+    type Output = O::Output;
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<O::Output, CryptopalsError> {
+        self.record_query(ciphertext.len())?;
+        self.inner.decrypt(ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        attacks::attack_ecb_one_byte_at_a_time,
+        oracles::{ecb_suffix::EcbSuffixOracle, profile::ProfileOracle},
+        random::SeededRng,
+    };
+
+    use super::*;
+
+    #[test]
+    fn instrumented_oracle_counts_queries() {
+        let mut rng = SeededRng::from_env();
+        let oracle = InstrumentedOracle::new(|data: &[u8]| data.repeat(2));
+        oracle.encrypt(b"abc");
+        oracle.try_encrypt(b"de").unwrap();
+
+        let stats = oracle.stats();
+        assert_eq!(
+            OracleStats {
+                queries: 2,
+                bytes_sent: 5,
+                bytes_received: 10
+            },
+            stats
+        );
+        assert_eq!(
+            "2 queries, 5 bytes sent, 10 bytes received",
+            stats.to_string()
+        );
+
+        oracle.reset();
+        assert_eq!(OracleStats::default(), oracle.stats());
+
+        let profiles = InstrumentedOracle::new(ProfileOracle::new(&mut rng));
+        let ciphertext = profiles.encrypt(b"foo@bar.com");
+        assert_eq!("user", profiles.decrypt(&ciphertext).unwrap()["role"]);
+        assert_eq!(2, profiles.stats().queries);
+    }
+
+    #[test]
+    fn instrumented_oracle_enforces_budget() {
+        let mut rng = SeededRng::from_env();
+        let secret = b"Budgets stop runaway attacks";
+        let oracle = EcbSuffixOracle::new(&mut rng, secret);
+
+        let unlimited = InstrumentedOracle::new(oracle.clone());
+        attack_ecb_one_byte_at_a_time(&unlimited).unwrap();
+        let needed = unlimited.stats().queries;
+
+        let limited = InstrumentedOracle::new(oracle).with_budget(needed / 2);
+        assert_eq!(
+            Err(CryptopalsError::QueryBudgetExceeded(needed / 2)),
+            attack_ecb_one_byte_at_a_time(&limited)
+        );
+        assert_eq!(needed / 2, limited.stats().queries);
+    }
+}
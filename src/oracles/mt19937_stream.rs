@@ -0,0 +1,16 @@
+use rand::{Rng, RngCore};
+
+use crate::random::encrypt_mt19937;
+
+use super::random_bytes;
+
+/// Encrypts 5 to 40 random bytes followed by the input under a 16-bit key. The key and
+/// prefix are picked once, and the key is returned to check attacks against.
+pub fn prefix_oracle<R: RngCore>(rng: &mut R) -> (u16, impl Fn(&[u8]) -> Vec<u8>) {
+    let key = rng.gen::<u16>();
+    let prefix_length = rng.gen_range(5..=40);
+    let prefix = random_bytes(rng, prefix_length);
+    let oracle = move |data: &[u8]| encrypt_mt19937(&[&prefix, data].concat(), key);
+
+    (key, oracle)
+}
@@ -0,0 +1,25 @@
+use rand::RngCore;
+
+use crate::{
+    block::{decrypt_aes_cbc, encrypt_aes_cbc},
+    error::CryptopalsError,
+};
+
+use super::random_bytes;
+
+/// Encrypts under a fresh random IV and returns `(iv, ciphertext)`.
+pub fn encrypt<R: RngCore>(
+    rng: &mut R,
+    data: &[u8],
+    key: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), CryptopalsError> {
+    let iv = random_bytes(rng, 16);
+    let ciphertext = encrypt_aes_cbc(data, key, Some(iv.clone()))?;
+
+    Ok((iv, ciphertext))
+}
+
+/// Whether the ciphertext decrypts to validly padded plaintext. Leaks nothing else.
+pub fn has_valid_padding(iv: &[u8], ciphertext: &[u8], key: &[u8]) -> bool {
+    decrypt_aes_cbc(ciphertext, key, Some(iv.to_vec())).is_ok()
+}
@@ -0,0 +1,11 @@
+use rand::RngCore;
+
+use crate::random::{Clock, Mt19937};
+
+/// A reset token made of MT19937 output seeded with the current time.
+pub fn password_reset_token(clock: &dyn Clock, length: usize) -> Vec<u8> {
+    let mut token = vec![0; length];
+    Mt19937::new(clock.now() as u32).fill_bytes(&mut token);
+
+    token
+}
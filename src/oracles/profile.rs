@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::{
+    block::{decrypt_aes_ecb, encrypt_aes_ecb},
+    error::CryptopalsError,
+};
+
+use super::{random_bytes, DecryptionOracle, EncryptionOracle};
+
+pub fn parse_query_string(query_string: &str) -> Result<HashMap<String, String>, CryptopalsError> {
+    let key_value_pairs = query_string.split('&');
+    key_value_pairs
+        .map(|key_value| {
+            key_value
+                .split_once('=')
+                .ok_or(CryptopalsError::InvalidQueryString(
+                    query_string.to_string(),
+                ))
+        })
+        .map(|pair| pair.map(|(k, v)| (k.to_string(), v.to_string())))
+        .collect()
+}
+
+pub fn profile_for(email: &str) -> String {
+    let email = email
+        .chars()
+        .filter(|&x| x != '&' && x != '=')
+        .collect::<String>();
+    format!("email={email}&uid=10&role=user")
+}
+
+pub fn encrypt_profile(email: &str, key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    encrypt_aes_ecb(profile_for(email).as_bytes(), key)
+}
+
+pub fn decrypt_profile(
+    ciphertext: &[u8],
+    key: &[u8],
+) -> Result<HashMap<String, String>, CryptopalsError> {
+    let plain = String::from_utf8(decrypt_aes_ecb(ciphertext, key)?)?;
+    parse_query_string(&plain)
+}
+
+/// Encrypts the profile for an email with AES-128-ECB under its own key, and decrypts
+/// profiles back into their fields.
+#[derive(Debug, Clone)]
+pub struct ProfileOracle {
+    key: Vec<u8>,
+}
+
+impl ProfileOracle {
+    pub fn new<R: RngCore>(rng: &mut R) -> Self {
+        Self {
+            key: random_bytes(rng, 16),
+        }
+    }
+}
+
+impl EncryptionOracle for ProfileOracle {
+    /// Each input byte is taken as one character of the email.
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        let email = data.iter().map(|&byte| byte as char).collect::<String>();
+        encrypt_profile(&email, &self.key).expect("Key is 16 bytes")
+    }
+}
+
+impl DecryptionOracle for ProfileOracle {
+    type Output = HashMap<String, String>;
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<HashMap<String, String>, CryptopalsError> {
+        decrypt_profile(ciphertext, &self.key)
+    }
+}
@@ -0,0 +1,32 @@
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::block::{encrypt_aes, BlockMode};
+
+use super::random_bytes;
+
+/// An oracle in a random `BlockMode`, returned alongside it.
+pub fn encryption_oracle<R: RngCore>(rng: &mut R) -> (BlockMode, impl Fn(&[u8]) -> Vec<u8>) {
+    fixed_mode_oracle(rng, &BlockMode::ALL)
+}
+
+/// Encrypts between random bytes in one of `modes`. The mode, key, IV and surrounding random
+/// bytes are picked once and reused on every call.
+pub fn fixed_mode_oracle<R: RngCore>(
+    rng: &mut R,
+    modes: &[BlockMode],
+) -> (BlockMode, impl Fn(&[u8]) -> Vec<u8>) {
+    let mode = *modes.choose(rng).expect("At least one mode");
+    let prefix_length = rng.gen_range(5..=10);
+    let prefix = random_bytes(rng, prefix_length);
+    let suffix_length = rng.gen_range(5..=10);
+    let suffix = random_bytes(rng, suffix_length);
+    let key = random_bytes(rng, 16);
+    let iv = random_bytes(rng, 16);
+
+    let oracle = move |data: &[u8]| {
+        let data = [&prefix, data, &suffix].concat();
+        encrypt_aes(mode, &data, &key, Some(iv.clone())).expect("Key and IV are 16 bytes")
+    };
+
+    (mode, oracle)
+}
@@ -0,0 +1,21 @@
+use crate::error::CryptopalsError;
+
+use super::EncryptionOracle;
+
+/// Refuses any input that is not ASCII.
+pub struct AsciiOnlyOracle<O>(pub O);
+
+impl<O: EncryptionOracle> EncryptionOracle for AsciiOnlyOracle<O> {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        self.try_encrypt(data).unwrap()
+    }
+
+    fn try_encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+        match data.is_ascii() {
+            true => self.0.try_encrypt(data),
+            false => Err(CryptopalsError::OracleFailure(
+                "Input is not ASCII".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use rand::{Rng, RngCore};
+
+use crate::random::{Clock, Mt19937, SimulatedClock};
+
+/// Waits a random while, seeds MT19937 with the current time, waits again and returns the
+/// seed alongside the first output.
+pub fn time_seeded_output<R: RngCore>(rng: &mut R, clock: &SimulatedClock) -> (u32, u32) {
+    clock.advance(rng.gen_range(40..=1000));
+    let seed = clock.now() as u32;
+    let output = Mt19937::new(seed).next_u32();
+    clock.advance(rng.gen_range(40..=1000));
+
+    (seed, output)
+}
@@ -0,0 +1,60 @@
+use rand::RngCore;
+
+use crate::{
+    block::{decrypt_aes_cbc, encrypt_aes_cbc},
+    error::CryptopalsError,
+};
+
+use super::{random_bytes, DecryptionOracle, EncryptionOracle};
+
+pub fn encrypt_user_data(data: &str, key: &[u8], iv: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    let data = format!(
+        "comment1=cooking%20MCs;userdata={};comment2=%20like%20a%20pound%20of%20bacon",
+        data
+    );
+    let url_encoded_data = data
+        .replace('=', "%3D")
+        .replace(';', "%3B")
+        .replace(' ', "%20");
+
+    encrypt_aes_cbc(url_encoded_data.as_bytes(), key, Some(iv.to_vec()))
+}
+
+pub fn is_admin(data: &[u8], key: &[u8], iv: &[u8]) -> Result<bool, CryptopalsError> {
+    let plain = decrypt_aes_cbc(data, key, Some(iv.to_vec()))?;
+    Ok(plain
+        .windows(b";admin=true;".len())
+        .any(|window| window == b";admin=true;"))
+}
+
+/// Encrypts escaped user data inside a fixed query string with AES-128-CBC under its own
+/// key and IV. Decrypting only tells whether the result grants admin.
+#[derive(Debug, Clone)]
+pub struct UserDataOracle {
+    key: Vec<u8>,
+    iv: Vec<u8>,
+}
+
+impl UserDataOracle {
+    pub fn new<R: RngCore>(rng: &mut R) -> Self {
+        Self {
+            key: random_bytes(rng, 16),
+            iv: random_bytes(rng, 16),
+        }
+    }
+}
+
+impl EncryptionOracle for UserDataOracle {
+    fn encrypt(&self, data: &[u8]) -> Vec<u8> {
+        encrypt_user_data(&String::from_utf8_lossy(data), &self.key, &self.iv)
+            .expect("Key and IV are 16 bytes")
+    }
+}
+
+impl DecryptionOracle for UserDataOracle {
+    type Output = bool;
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<bool, CryptopalsError> {
+        is_admin(ciphertext, &self.key, &self.iv)
+    }
+}
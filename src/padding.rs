@@ -0,0 +1,306 @@
+use std::cell::RefCell;
+
+use rand::RngCore;
+
+use crate::error::CryptopalsError;
+
+/// A scheme for filling the last block. `unpad` rejects anything `pad` could not have produced.
+/// Block sizes must fit the length byte, from 1 to 255.
+pub trait Padding {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError>;
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError>;
+}
+
+/// Every padding byte holds the padding length.
+pub struct Pkcs7;
+
+/// Zeros followed by a length byte.
+pub struct AnsiX923;
+
+/// Random bytes drawn from its own generator, followed by a length byte.
+pub struct Iso10126<R> {
+    rng: RefCell<R>,
+}
+
+/// A 0x80 byte followed by zeros.
+pub struct Iso7816;
+
+/// Zeros up to the block boundary, nothing when already aligned. Trailing zeros in the
+/// data are lost on unpad.
+pub struct ZeroPadding;
+
+/// Leaves the data untouched. Block modes then require aligned input.
+pub struct NoPadding;
+
+impl Padding for Pkcs7 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        pkcs7_padding(data, block_size)
+    }
+
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        strip_pkcs7_padding(data, block_size)
+    }
+}
+
+impl Padding for AnsiX923 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        let padding_length = padding_length(data, block_size)?;
+        let mut padded = [data, &vec![0; padding_length]].concat();
+        *padded.last_mut().expect("Padding is not empty") = padding_length as u8;
+
+        Ok(padded)
+    }
+
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        strip_length_padding(data, block_size, |_| Some(0))
+    }
+}
+
+impl<R: RngCore> Iso10126<R> {
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+impl<R: RngCore> Padding for Iso10126<R> {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        let padding_length = padding_length(data, block_size)?;
+        let mut padding = vec![0; padding_length];
+        self.rng.borrow_mut().fill_bytes(&mut padding);
+        *padding.last_mut().expect("Padding is not empty") = padding_length as u8;
+
+        Ok([data, &padding].concat())
+    }
+
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        strip_length_padding(data, block_size, |_| None)
+    }
+}
+
+impl Padding for Iso7816 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        let padding_length = padding_length(data, block_size)?;
+        let mut padded = [data, &vec![0; padding_length]].concat();
+        padded[data.len()] = 0x80;
+
+        Ok(padded)
+    }
+
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        let last_block = last_block(data, block_size)?;
+        match last_block.iter().rposition(|&byte| byte != 0) {
+            Some(position) if last_block[position] == 0x80 => {
+                Ok(data[..data.len() - block_size + position].to_vec())
+            }
+            _ => Err(CryptopalsError::InvalidPadding),
+        }
+    }
+}
+
+impl Padding for ZeroPadding {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        let padding_length = padding_length(data, block_size)? % block_size;
+        Ok([data, &vec![0; padding_length]].concat())
+    }
+
+    fn unpad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        if !data.len().is_multiple_of(block_size) {
+            return Err(CryptopalsError::InvalidPadding);
+        }
+
+        let length = data
+            .iter()
+            .rposition(|&byte| byte != 0)
+            .map_or(0, |i| i + 1);
+        Ok(data[..length].to_vec())
+    }
+}
+
+impl Padding for NoPadding {
+    fn pad(&self, data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        padding_length(data, block_size)?;
+        Ok(data.to_vec())
+    }
+
+    fn unpad(&self, data: &[u8], _block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+        Ok(data.to_vec())
+    }
+}
+
+pub fn pkcs7_padding(data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+    let padding_length = padding_length(data, block_size)?;
+    Ok([data, &vec![padding_length as u8; padding_length]].concat())
+}
+
+/// Bytes needed to reach the next block boundary, a whole block when already aligned.
+fn padding_length(data: &[u8], block_size: usize) -> Result<usize, CryptopalsError> {
+    if !(1..=255).contains(&block_size) {
+        return Err(CryptopalsError::InvalidBlockSize(block_size));
+    }
+
+    Ok(block_size - data.len() % block_size)
+}
+
+/// Removes PKCS#7 padding, rejecting anything `pkcs7_padding` could not have produced.
+/// The whole last block is checked so timing does not depend on where the padding breaks.
+pub fn strip_pkcs7_padding(data: &[u8], block_size: usize) -> Result<Vec<u8>, CryptopalsError> {
+    strip_length_padding(data, block_size, Some)
+}
+
+fn last_block(data: &[u8], block_size: usize) -> Result<&[u8], CryptopalsError> {
+    if data.is_empty() || !data.len().is_multiple_of(block_size) {
+        return Err(CryptopalsError::InvalidPadding);
+    }
+
+    Ok(&data[data.len() - block_size..])
+}
+
+/// Strips padding that ends in a length byte. `filler` gives the byte expected in the rest
+/// of the padding for a given length, or `None` when any byte is allowed.
+fn strip_length_padding<F>(
+    data: &[u8],
+    block_size: usize,
+    filler: F,
+) -> Result<Vec<u8>, CryptopalsError>
+where
+    F: Fn(u8) -> Option<u8>,
+{
+    let last_block = last_block(data, block_size)?;
+    let pad_length = *last_block.last().expect("Block is not empty");
+    let mut invalid = (pad_length == 0) as u8 | (pad_length as usize > block_size) as u8;
+    if let Some(filler) = filler(pad_length) {
+        for (i, &byte) in last_block.iter().rev().enumerate().skip(1) {
+            let in_padding = ((i < pad_length as usize) as u8).wrapping_neg();
+            invalid |= in_padding & (byte ^ filler);
+        }
+    }
+
+    if invalid != 0 {
+        return Err(CryptopalsError::InvalidPadding);
+    }
+
+    Ok(data[..data.len() - pad_length as usize].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::random::SeededRng;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn strip_undoes_padding(data in prop::collection::vec(any::<u8>(), 0..100), block_size in 1usize..=255) {
+            let padded = pkcs7_padding(&data, block_size).unwrap();
+            prop_assert_eq!(Ok(data), strip_pkcs7_padding(&padded, block_size));
+        }
+
+        #[test]
+        fn strip_only_accepts_padded_data(data in prop::collection::vec(any::<u8>(), 0..64), block_size in 1usize..=32) {
+            if let Ok(stripped) = strip_pkcs7_padding(&data, block_size) {
+                prop_assert_eq!(Ok(data), pkcs7_padding(&stripped, block_size));
+            }
+        }
+
+        #[test]
+        fn strip_rejects_corrupted_padding(data in prop::collection::vec(any::<u8>(), 0..64), block_size in 2usize..=32, flip in 1u8..) {
+            let mut padded = pkcs7_padding(&data, block_size).unwrap();
+            let pad_length = *padded.last().unwrap() as usize;
+            // A single padding byte is the length byte, which may corrupt into other valid padding.
+            prop_assume!(pad_length >= 2);
+            let length = padded.len();
+            padded[length - pad_length] ^= flip;
+            prop_assert_eq!(Err(CryptopalsError::InvalidPadding), strip_pkcs7_padding(&padded, block_size));
+        }
+
+        #[test]
+        fn every_scheme_roundtrips(data in prop::collection::vec(any::<u8>(), 0..64), block_size in 1usize..=32) {
+            let iso10126 = Iso10126::new(SeededRng::from_env());
+            let schemes: [&dyn Padding; 4] = [&Pkcs7, &AnsiX923, &iso10126, &Iso7816];
+            for scheme in schemes {
+                let padded = scheme.pad(&data, block_size).unwrap();
+                prop_assert!(padded.len() > data.len() && padded.len().is_multiple_of(block_size));
+                prop_assert_eq!(Ok(data.clone()), scheme.unpad(&padded, block_size));
+            }
+        }
+    }
+
+    #[test]
+    fn padding_rejects_invalid_block_sizes() {
+        let iso10126 = Iso10126::new(SeededRng::from_env());
+        let schemes: [&dyn Padding; 6] = [
+            &Pkcs7,
+            &AnsiX923,
+            &iso10126,
+            &Iso7816,
+            &ZeroPadding,
+            &NoPadding,
+        ];
+        for scheme in schemes {
+            for block_size in [0, 256, 1000] {
+                assert_eq!(
+                    Err(CryptopalsError::InvalidBlockSize(block_size)),
+                    scheme.pad(b"YELLOW SUBMARINE", block_size)
+                );
+            }
+            assert!(scheme.pad(b"YELLOW SUBMARINE", 255).is_ok());
+        }
+    }
+
+    #[test]
+    fn padding_schemes() {
+        let data = b"YELLOW SUBMARINE\x00\x01";
+        assert_eq!(
+            b"YELLOW SUBMARINE\x00\x01\x00\x00\x00\x00\x00\x06".to_vec(),
+            AnsiX923.pad(data, 8).unwrap()
+        );
+        assert_eq!(
+            b"YELLOW SUBMARINE\x00\x01\x80\x00\x00\x00\x00\x00".to_vec(),
+            Iso7816.pad(data, 8).unwrap()
+        );
+        assert_eq!(
+            b"YELLOW SUBMARINE\x00\x01\x00\x00\x00\x00\x00\x00".to_vec(),
+            ZeroPadding.pad(data, 8).unwrap()
+        );
+        assert_eq!(
+            b"YELLOW SUBMARINE".to_vec(),
+            ZeroPadding.pad(b"YELLOW SUBMARINE", 8).unwrap()
+        );
+        assert_eq!(
+            Ok(b"YELLOW SUBMARINE\x00\x01".to_vec()),
+            ZeroPadding.unpad(&ZeroPadding.pad(data, 8).unwrap(), 8)
+        );
+        assert_eq!(data.to_vec(), NoPadding.pad(data, 8).unwrap());
+
+        let iso10126 = Iso10126::new(SeededRng::from_env());
+        let padded = iso10126.pad(data, 8).unwrap();
+        assert_eq!(24, padded.len());
+        assert_eq!(6, padded[23]);
+        assert_eq!(
+            padded,
+            Iso10126::new(SeededRng::from_env()).pad(data, 8).unwrap(),
+            "Same seed, same padding"
+        );
+
+        assert_eq!(
+            Err(CryptopalsError::InvalidPadding),
+            AnsiX923.unpad(b"YELLOW SUBMARINE\x00\x01\x00\x00\x00\x01\x00\x06", 8)
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidPadding),
+            Iso7816.unpad(b"YELLOW SUBMARINE\x00\x01\x80\x00\x00\x00\x00\x01", 8)
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidPadding),
+            Iso7816.unpad(&[0; 8], 8)
+        );
+        assert_eq!(
+            Err(CryptopalsError::InvalidPadding),
+            iso10126.unpad(&[9; 8], 8)
+        );
+    }
+}
@@ -0,0 +1,9 @@
+mod clock;
+mod mt19937;
+mod mt19937_stream;
+mod seeded;
+
+pub use clock::{Clock, SimulatedClock, SystemClock};
+pub use mt19937::{untemper, untemper_64, Mt19937, Mt19937_64};
+pub use mt19937_stream::{decrypt_mt19937, encrypt_mt19937};
+pub use seeded::{SeededRng, DEFAULT_SEED, SEED_VARIABLE};
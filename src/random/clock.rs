@@ -0,0 +1,44 @@
+use std::{
+    cell::Cell,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A source of Unix timestamps in seconds.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is after the epoch")
+            .as_secs()
+    }
+}
+
+/// A clock that only moves when told to, so time-based attacks run instantly.
+#[derive(Debug, Clone)]
+pub struct SimulatedClock {
+    now: Cell<u64>,
+}
+
+impl SimulatedClock {
+    pub fn new(start: u64) -> Self {
+        Self {
+            now: Cell::new(start),
+        }
+    }
+
+    pub fn advance(&self, seconds: u64) {
+        self.now.set(self.now.get() + seconds);
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+}
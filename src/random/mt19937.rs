@@ -0,0 +1,252 @@
+use rand::RngCore;
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908b0df;
+const UPPER_MASK: u32 = 0x80000000;
+const LOWER_MASK: u32 = 0x7fffffff;
+
+const N_64: usize = 312;
+const M_64: usize = 156;
+const MATRIX_A_64: u64 = 0xb5026f5aa96619e9;
+const UPPER_MASK_64: u64 = 0xffffffff80000000;
+const LOWER_MASK_64: u64 = 0x7fffffff;
+
+/// The 32-bit Mersenne Twister.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub const STATE_SIZE: usize = N;
+
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0; N];
+        state[0] = seed;
+        for i in 1..N {
+            let previous = state[i - 1];
+            state[i] = 1812433253u32
+                .wrapping_mul(previous ^ (previous >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        Self { state, index: N }
+    }
+
+    /// A generator whose next outputs come from `state` once twisted, as after 624 outputs.
+    pub fn from_state(state: [u32; N]) -> Self {
+        Self { state, index: N }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper(y)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mut next = self.state[(i + M) % N] ^ (y >> 1);
+            if y & 1 == 1 {
+                next ^= MATRIX_A;
+            }
+            self.state[i] = next;
+        }
+
+        self.index = 0;
+    }
+}
+
+impl RngCore for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        Mt19937::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = Mt19937::next_u32(self) as u64;
+        let high = Mt19937::next_u32(self) as u64;
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = Mt19937::next_u32(self).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The 64-bit Mersenne Twister.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mt19937_64 {
+    state: [u64; N_64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    pub const STATE_SIZE: usize = N_64;
+
+    pub fn new(seed: u64) -> Self {
+        let mut state = [0; N_64];
+        state[0] = seed;
+        for i in 1..N_64 {
+            let previous = state[i - 1];
+            state[i] = 6364136223846793005u64
+                .wrapping_mul(previous ^ (previous >> 62))
+                .wrapping_add(i as u64);
+        }
+
+        Self { state, index: N_64 }
+    }
+
+    /// A generator whose next outputs come from `state` once twisted, as after 312 outputs.
+    pub fn from_state(state: [u64; N_64]) -> Self {
+        Self { state, index: N_64 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper_64(y)
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N_64 {
+            let y = (self.state[i] & UPPER_MASK_64) | (self.state[(i + 1) % N_64] & LOWER_MASK_64);
+            let mut next = self.state[(i + M_64) % N_64] ^ (y >> 1);
+            if y & 1 == 1 {
+                next ^= MATRIX_A_64;
+            }
+            self.state[i] = next;
+        }
+
+        self.index = 0;
+    }
+}
+
+impl RngCore for Mt19937_64 {
+    fn next_u32(&mut self) -> u32 {
+        Mt19937_64::next_u64(self) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Mt19937_64::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = Mt19937_64::next_u64(self).to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+fn temper(y: u32) -> u32 {
+    let y = y ^ (y >> 11);
+    let y = y ^ ((y << 7) & 0x9d2c5680);
+    let y = y ^ ((y << 15) & 0xefc60000);
+    y ^ (y >> 18)
+}
+
+fn temper_64(y: u64) -> u64 {
+    let y = y ^ ((y >> 29) & 0x5555555555555555);
+    let y = y ^ ((y << 17) & 0x71d67fffeda60000);
+    let y = y ^ ((y << 37) & 0xfff7eee000000000);
+    y ^ (y >> 43)
+}
+
+/// Recovers the state word behind a 32-bit output.
+pub fn untemper(y: u32) -> u32 {
+    let y = undo_right_shift(y as u64, 18, u32::MAX as u64);
+    let y = undo_left_shift(y, 15, 0xefc60000);
+    let y = undo_left_shift(y, 7, 0x9d2c5680);
+    undo_right_shift(y, 11, u32::MAX as u64) as u32
+}
+
+/// Recovers the state word behind a 64-bit output.
+pub fn untemper_64(y: u64) -> u64 {
+    let y = undo_right_shift(y, 43, u64::MAX);
+    let y = undo_left_shift(y, 37, 0xfff7eee000000000);
+    let y = undo_left_shift(y, 17, 0x71d67fffeda60000);
+    undo_right_shift(y, 29, 0x5555555555555555)
+}
+
+/// Inverts `y ^ ((y >> shift) & mask)`. Each pass fixes `shift` more of the top bits.
+fn undo_right_shift(y: u64, shift: u32, mask: u64) -> u64 {
+    let mut x = y;
+    for _ in 0..64u32.div_ceil(shift) {
+        x = y ^ ((x >> shift) & mask);
+    }
+
+    x
+}
+
+/// Inverts `y ^ ((y << shift) & mask)`. Each pass fixes `shift` more of the bottom bits.
+fn undo_left_shift(y: u64, shift: u32, mask: u64) -> u64 {
+    let mut x = y;
+    for _ in 0..64u32.div_ceil(shift) {
+        x = y ^ ((x << shift) & mask);
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mt19937_reference_outputs() {
+        let mut rng = Mt19937::new(5489);
+        assert_eq!(3499211612, rng.next_u32());
+        assert_eq!(581869302, rng.next_u32());
+        assert_eq!(3890346734, rng.next_u32());
+
+        let mut rng = Mt19937::new(5489);
+        assert_eq!(
+            4123659995,
+            (0..10000).map(|_| rng.next_u32()).last().unwrap()
+        );
+
+        let mut rng = Mt19937_64::new(5489);
+        assert_eq!(14514284786278117030, rng.next_u64());
+        let mut rng = Mt19937_64::new(5489);
+        assert_eq!(
+            9981545732273789042,
+            (0..10000).map(|_| rng.next_u64()).last().unwrap()
+        );
+    }
+
+    #[test]
+    fn untemper_inverts_temper() {
+        for y in [0, 1, 0x80000000, 0xdeadbeef, u32::MAX] {
+            assert_eq!(y, untemper(temper(y)));
+        }
+        for y in [0, 1, 1 << 63, 0xdeadbeefcafebabe, u64::MAX] {
+            assert_eq!(y, untemper_64(temper_64(y)));
+        }
+    }
+}
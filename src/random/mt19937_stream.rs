@@ -0,0 +1,31 @@
+use rand::RngCore;
+
+use crate::xor::xor_bytes;
+
+use super::Mt19937;
+
+/// Xors `data` with the bytes of an MT19937 generator seeded with a 16-bit key.
+pub fn encrypt_mt19937(data: &[u8], key: u16) -> Vec<u8> {
+    let mut keystream = vec![0; data.len()];
+    Mt19937::new(key as u32).fill_bytes(&mut keystream);
+
+    xor_bytes(data, &keystream)
+}
+
+pub fn decrypt_mt19937(data: &[u8], key: u16) -> Vec<u8> {
+    encrypt_mt19937(data, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mt19937_stream_roundtrip() {
+        let plain = b"Mersenne Twister makes a poor stream cipher";
+        let ciphertext = encrypt_mt19937(plain, 0xbeef);
+        assert_ne!(plain.to_vec(), ciphertext);
+        assert_eq!(plain.to_vec(), decrypt_mt19937(&ciphertext, 0xbeef));
+        assert_ne!(plain.to_vec(), decrypt_mt19937(&ciphertext, 0xbeee));
+    }
+}
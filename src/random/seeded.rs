@@ -0,0 +1,98 @@
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Environment variable read by `SeededRng::from_env`.
+pub const SEED_VARIABLE: &str = "CRYPTOPALS_SEED";
+
+/// Seed used when `SEED_VARIABLE` is not set, so runs are reproducible by default.
+pub const DEFAULT_SEED: u64 = 0x5eed_c0de;
+
+/// A seeded generator that prints its seed when dropped during a panic, so a failing
+/// attack can be replayed with the same randomness.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seeds from `SEED_VARIABLE`, falling back to `DEFAULT_SEED` when it is not set. Panics
+    /// if it is set to anything but a `u64`.
+    pub fn from_env() -> Self {
+        Self::new(parse_seed(std::env::var(SEED_VARIABLE).ok().as_deref()))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl Drop for SeededRng {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            eprintln!(
+                "Random seed was {0}, rerun with {SEED_VARIABLE}={0}",
+                self.seed
+            );
+        }
+    }
+}
+
+fn parse_seed(value: Option<&str>) -> u64 {
+    match value {
+        Some(seed) => seed
+            .parse()
+            .unwrap_or_else(|_| panic!("{SEED_VARIABLE} must be a u64, got {seed:?}")),
+        None => DEFAULT_SEED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_rng_is_reproducible() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(a.next_u64(), SeededRng::new(43).next_u64());
+        assert_eq!(42, a.seed());
+    }
+
+    #[test]
+    fn parse_seed_works() {
+        assert_eq!(DEFAULT_SEED, parse_seed(None));
+        assert_eq!(12345, parse_seed(Some("12345")));
+    }
+
+    #[test]
+    #[should_panic(expected = "CRYPTOPALS_SEED must be a u64")]
+    fn parse_seed_rejects_garbage() {
+        parse_seed(Some("not a seed"));
+    }
+}
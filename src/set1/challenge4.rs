@@ -1,33 +1,8 @@
-use super::challenge3::{break_single_xor_base64, break_single_xor_hex, DecryptMetadata};
-
-pub fn detect_single_xor_ciphertext_hex(sentences: &[&str]) -> Option<DecryptMetadata> {
-    detect_single_xor_ciphertext(sentences, break_single_xor_hex)
-}
-
-pub fn detect_single_xor_ciphertext_base64(sentences: &[&str]) -> Option<DecryptMetadata> {
-    detect_single_xor_ciphertext(sentences, break_single_xor_base64)
-}
-
-fn detect_single_xor_ciphertext(
-    sentences: &[&str],
-    bruteforce_fn: fn(&str) -> DecryptMetadata,
-) -> Option<DecryptMetadata> {
-    let decryped_data = sentences
-        .iter()
-        .map(|sentence| bruteforce_fn(sentence))
-        .filter(|decrypt_metadata| decrypt_metadata.key != '.')
-        .min_by(|a, b| {
-            a.english_similarity
-                .partial_cmp(&b.english_similarity)
-                .unwrap_or(std::cmp::Ordering::Greater)
-        });
-
-    decryped_data
-}
+pub use crate::attacks::{detect_single_xor_ciphertext_base64, detect_single_xor_ciphertext_hex};
 
 #[cfg(test)]
 mod tests {
-    use crate::set1::read_set1_resource;
+    use crate::{analysis::UnigramChiSquared, set1::read_set1_resource};
 
     use super::*;
 
@@ -35,10 +10,13 @@ mod tests {
     fn detect_single_character_xor_works() {
         let file_data = read_set1_resource("challenge4.txt");
         let lines = file_data.lines().collect::<Vec<&str>>();
-        let decrypt_metadata = detect_single_xor_ciphertext_hex(&lines).unwrap();
-        assert_eq!('5', decrypt_metadata.key);
+        let decrypt_metadata =
+            detect_single_xor_ciphertext_hex(&lines, &UnigramChiSquared::default())
+                .unwrap()
+                .unwrap();
+        assert_eq!(b'5', decrypt_metadata.key);
         assert_eq!(
-            "Now that the party is jumping\n".to_string(),
+            b"Now that the party is jumping\n".to_vec(),
             decrypt_metadata.decrypted_data
         );
     }
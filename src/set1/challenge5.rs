@@ -1,24 +1,4 @@
-use base64::prelude::*;
-
-use super::challenge2::xor_bytes;
-
-pub fn encrypt_repeating_key_xor_to_hex(data: &str, key: &str) -> String {
-    hex::encode(encrypt_repeating_key_xor(data, key))
-}
-
-pub fn encrypt_repeating_key_xor_to_base64(data: &str, key: &str) -> String {
-    BASE64_STANDARD.encode(encrypt_repeating_key_xor(data, key))
-}
-
-fn encrypt_repeating_key_xor(data: &str, key: &str) -> Vec<u8> {
-    let data_bytes = data.as_bytes();
-    let key_bytes = key.as_bytes();
-
-    data_bytes
-        .chunks(key_bytes.len())
-        .flat_map(|chunk| xor_bytes(chunk, key_bytes))
-        .collect::<Vec<u8>>()
-}
+pub use crate::xor::{encrypt_repeating_key_xor_to_base64, encrypt_repeating_key_xor_to_hex};
 
 #[cfg(test)]
 mod tests {
@@ -33,7 +13,7 @@ mod tests {
 
         assert_eq!(
             expected_encrypted_data,
-            encrypt_repeating_key_xor_to_hex(data, key)
+            encrypt_repeating_key_xor_to_hex(data.as_bytes(), key.as_bytes()).unwrap()
         );
     }
 }
@@ -1,34 +1,4 @@
-use std::collections::HashMap;
-
-pub fn detect_aes_128_ecb(data: &[Vec<u8>]) -> Option<String> {
-    let mut detected_aes_line = String::new();
-    let mut current_max_count = 0;
-    for line in data {
-        let max_count = max_repeated_block(line);
-        if max_count > current_max_count {
-            current_max_count = max_count;
-            detected_aes_line = hex::encode(line);
-        }
-    }
-
-    if detected_aes_line.is_empty() {
-        None
-    } else {
-        Some(detected_aes_line)
-    }
-}
-
-pub fn max_repeated_block(data: &[u8]) -> u128 {
-    let mut count_chunks = HashMap::<Vec<u8>, u128>::new();
-    for chunk in data.chunks(16) {
-        *count_chunks.entry(chunk.to_vec()).or_insert(1) += 1;
-    }
-
-    count_chunks
-        .into_values()
-        .max()
-        .expect("Have at least one element")
-}
+pub use crate::analysis::{detect_aes_128_ecb, max_repeated_block};
 
 #[cfg(test)]
 mod tests {
@@ -37,7 +7,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn decrypt_aes_128_ecb_works() {
+    fn decrypt_aes_ecb_works() {
         let file_data = read_set1_resource("challenge8.txt");
         let data = file_data
             .lines()
@@ -1,133 +1,147 @@
-use std::collections::HashMap;
-
-use base64::prelude::*;
-
-use crate::set1::challenge8::max_repeated_block;
-
-use super::challenge10::encrypt_aes_128_ecb;
-
-const UNKNOWN_STRING: &str = "Um9sbGluJyBpbiBteSA1LjAKV2l0aCBteSByYWctdG9wIGRvd24gc28gbXkgaGFpciBjYW4gYmxvdwpUaGUgZ2lybGllcyBvbiBzdGFuZGJ5IHdhdmluZyBqdXN0IHRvIHNheSBoaQpEaWQgeW91IHN0b3A/IE5vLCBJIGp1c3QgZHJvdmUgYnkK";
-
-pub fn encrypt(data: &[u8], key: &[u8]) -> Vec<u8> {
-    let unknown_string = BASE64_STANDARD.decode(UNKNOWN_STRING).expect("Valid data");
-    let data = [data, &unknown_string].concat();
-
-    encrypt_aes_128_ecb(&data, key)
-}
-
-pub fn attack_ecb_one_byte_at_a_time<F>(encrypt_fn: F) -> String
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let (block_size, padding_length) = compute_block_size_and_padding_length(&encrypt_fn);
-    if !is_ecb(&encrypt_fn, block_size) {
-        panic!("Data not encryped with ECB");
-    }
-
-    let mut plain = vec![0; block_size - 1];
-    let num_target_bytes = encrypt_fn(&[]).len() - padding_length;
-    for i in 0..num_target_bytes {
-        let crafted_prefix = &plain[plain.len() - (block_size - 1)..];
-        let ciphertext_block_to_character =
-            brute_force_ciphertext_block(&encrypt_fn, crafted_prefix, 0, block_size);
-
-        let raw_prefix = vec![0; block_size - 1 - (i % block_size)];
-        let ciphertext = encrypt_fn(&raw_prefix);
-        let start = (i / block_size) * block_size;
-        let end = start + block_size;
-        let character = ciphertext_block_to_character
-            .get(&ciphertext[start..end])
-            .expect("Exists");
-
-        plain.push(*character);
-    }
-
-    String::from_utf8(plain[block_size - 1..].to_vec()).expect("Valid plain message")
-}
-
-pub fn compute_block_size_and_padding_length<F>(encryption_fn: F) -> (usize, usize)
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let mut i = 0;
-    let mut len_diff = 0;
-    while len_diff == 0 {
-        len_diff = encryption_fn(&vec![0; i + 1]).len() - encryption_fn(&vec![0; i]).len();
-        i += 1;
-    }
-
-    (len_diff, i)
-}
-
-pub fn is_ecb<F>(encryption_fn: F, block_size: usize) -> bool
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let plain = vec![0; block_size * 100];
-    let ciphertext = encryption_fn(&plain);
-
-    max_repeated_block(&ciphertext) >= 90
-}
-
-pub fn brute_force_ciphertext_block<F>(
-    encryption_fn: F,
-    prefix: &[u8],
-    block_position: usize,
-    block_size: usize,
-) -> HashMap<Vec<u8>, u8>
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let mut encrypted_block_to_character = HashMap::new();
-    for i in 0..=255u8 {
-        let prefix_with_character = [prefix, &[i]].concat().to_vec();
-        let encrypted_data = encryption_fn(&prefix_with_character);
-        let start = block_position * block_size;
-        let end = start + block_size;
-        let encrypted_block = encrypted_data[start..end].to_vec();
-        encrypted_block_to_character.insert(encrypted_block, i);
-    }
-
-    encrypted_block_to_character
-}
+pub use crate::{
+    analysis::is_ecb,
+    attacks::{
+        attack_ecb_one_byte_at_a_time, attack_ecb_one_byte_at_a_time_with_order,
+        brute_force_ciphertext_block, compute_block_size_and_padding_length, GuessOrder,
+    },
+    oracles::{
+        ecb_suffix::{encrypt, EcbSuffixOracle},
+        EncryptionOracle, UNKNOWN_STRING,
+    },
+};
 
 #[cfg(test)]
 mod tests {
     use base64::prelude::*;
 
-    use crate::set2::{
-        challenge11::random_bytes,
-        challenge12::{attack_ecb_one_byte_at_a_time, is_ecb, UNKNOWN_STRING},
+    use crate::{
+        error::CryptopalsError,
+        oracles::{instrumented::InstrumentedOracle, testing::AsciiOnlyOracle},
+        random::SeededRng,
+        set2::{
+            challenge10::encrypt_aes_cbc,
+            challenge11::random_bytes,
+            challenge12::{attack_ecb_one_byte_at_a_time, is_ecb, UNKNOWN_STRING},
+        },
     };
 
-    use super::{compute_block_size_and_padding_length, encrypt};
+    use super::{
+        attack_ecb_one_byte_at_a_time_with_order, compute_block_size_and_padding_length, encrypt,
+        EcbSuffixOracle, GuessOrder,
+    };
 
     #[test]
     fn test_discover_block_size() {
-        let key = random_bytes(16);
-        let encryption_fn = |data: &[u8]| encrypt(data, &key);
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        let encryption_fn = |data: &[u8]| encrypt(data, &key).unwrap();
 
-        let (block_size, _) = compute_block_size_and_padding_length(encryption_fn);
+        let (block_size, _) = compute_block_size_and_padding_length(&encryption_fn).unwrap();
         assert_eq!(16, block_size);
     }
 
     #[test]
     fn test_is_ecb() {
-        let key = random_bytes(16);
-        let encryption_fn = |data: &[u8]| encrypt(data, &key);
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        let encryption_fn = |data: &[u8]| encrypt(data, &key).unwrap();
 
-        let (block_size, _) = compute_block_size_and_padding_length(encryption_fn);
-        assert!(is_ecb(encryption_fn, block_size));
-        assert!(!is_ecb(encryption_fn, 8));
+        let (block_size, _) = compute_block_size_and_padding_length(&encryption_fn).unwrap();
+        assert!(is_ecb(&encryption_fn, block_size).unwrap());
+        assert!(!is_ecb(&encryption_fn, 8).unwrap());
     }
 
     #[test]
     fn test_attack_ecb() {
-        let key = random_bytes(16);
-        let encryption_fn = |data: &[u8]| encrypt(data, &key);
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        let encryption_fn = |data: &[u8]| encrypt(data, &key).unwrap();
+        assert_eq!(
+            String::from_utf8(BASE64_STANDARD.decode(UNKNOWN_STRING).unwrap()).unwrap(),
+            attack_ecb_one_byte_at_a_time(&encryption_fn).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_attack_ecb_aes_256() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 32);
+        let encryption_fn = |data: &[u8]| encrypt(data, &key).unwrap();
         assert_eq!(
             String::from_utf8(BASE64_STANDARD.decode(UNKNOWN_STRING).unwrap()).unwrap(),
-            attack_ecb_one_byte_at_a_time(encryption_fn)
+            attack_ecb_one_byte_at_a_time(&encryption_fn).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_attack_ecb_batches_guesses() {
+        let mut rng = SeededRng::from_env();
+        let unknown_string = BASE64_STANDARD.decode(UNKNOWN_STRING).unwrap();
+        let oracle = EcbSuffixOracle::new(&mut rng, &unknown_string);
+
+        let all_bytes = InstrumentedOracle::new(oracle.clone());
+        let printable_first = InstrumentedOracle::new(oracle);
+        for (order, oracle) in [
+            (GuessOrder::AllBytes, &all_bytes),
+            (GuessOrder::PrintableFirst, &printable_first),
+        ] {
+            assert_eq!(
+                String::from_utf8(unknown_string.clone()).unwrap(),
+                attack_ecb_one_byte_at_a_time_with_order(oracle, order).unwrap()
+            );
+            // One query per guessed byte rather than 257.
+            assert!(oracle.stats().queries * 10 < unknown_string.len() * 257);
+        }
+        assert!(printable_first.stats().bytes_sent < all_bytes.stats().bytes_sent);
+
+        let secret = "Non-ASCII bytes fall back to the rest: naïve café";
+        let oracle = EcbSuffixOracle::new(&mut rng, secret.as_bytes());
+        assert_eq!(
+            secret,
+            attack_ecb_one_byte_at_a_time_with_order(&oracle, GuessOrder::PrintableFirst).unwrap()
         );
     }
+
+    #[test]
+    fn test_attack_ecb_propagates_oracle_errors() {
+        let mut rng = SeededRng::from_env();
+        let oracle = AsciiOnlyOracle(EcbSuffixOracle::new(&mut rng, b"secret"));
+        assert_eq!(
+            Err(CryptopalsError::OracleFailure(
+                "Input is not ASCII".to_string()
+            )),
+            attack_ecb_one_byte_at_a_time(&oracle)
+        );
+    }
+
+    #[test]
+    fn test_attack_independent_oracles_concurrently() {
+        let mut rng = SeededRng::from_env();
+        let secrets = ["first secret", "second secret", "third secret"];
+        let oracles = secrets
+            .iter()
+            .map(|secret| EcbSuffixOracle::new(&mut rng, secret.as_bytes()))
+            .collect::<Vec<EcbSuffixOracle>>();
+
+        std::thread::scope(|scope| {
+            let attacks = oracles
+                .iter()
+                .map(|oracle| scope.spawn(|| attack_ecb_one_byte_at_a_time(oracle)))
+                .collect::<Vec<_>>();
+            for (secret, attack) in secrets.iter().zip(attacks) {
+                assert_eq!(*secret, attack.join().unwrap().unwrap());
+            }
+        });
+    }
+
+    #[test]
+    fn test_attack_ecb_rejects_non_ecb_oracle() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        let encryption_fn = |data: &[u8]| encrypt_aes_cbc(data, &key, None).unwrap();
+        assert!(matches!(
+            attack_ecb_one_byte_at_a_time(&encryption_fn),
+            Err(CryptopalsError::OracleFailure(_))
+        ));
+    }
 }
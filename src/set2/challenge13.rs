@@ -1,83 +1,21 @@
-use std::collections::HashMap;
-
-use crate::{set1::challenge7::decrypt_aes_128_ecb, set2::challenge12::is_ecb};
-
-use super::{challenge10::encrypt_aes_128_ecb, challenge12::compute_block_size_and_padding_length};
-
-pub fn parse_query_string(query_string: &str) -> HashMap<String, String> {
-    let key_value_pairs = query_string.split('&');
-    key_value_pairs
-        .map(|key_value| key_value.split_once('=').expect("Correct query string"))
-        .map(|(k, v)| (k.to_string(), v.to_string()))
-        .collect()
-}
-
-pub fn profile_for(email: &str) -> String {
-    let email = email
-        .chars()
-        .filter(|&x| x != '&' && x != '=')
-        .collect::<String>();
-    format!("email={email}&uid=10&role=user")
-}
-
-pub fn encrypt_profile(email: &str, key: &[u8]) -> Vec<u8> {
-    encrypt_aes_128_ecb(profile_for(email).as_bytes(), key)
-}
-
-pub fn decrypt_profile(ciphertext: &[u8], key: &[u8]) -> HashMap<String, String> {
-    let plain = decrypt_aes_128_ecb(ciphertext, key).expect("Valid profile");
-    parse_query_string(&plain)
-}
-
-pub fn ecb_cut_and_paste_attack<F>(encrypt_fn: F) -> Vec<u8>
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let (block_size, original_padding_length) = compute_block_size_and_padding_length(&encrypt_fn);
-    if !is_ecb(&encrypt_fn, block_size) {
-        panic!("Data not encryped with ECB");
-    }
-
-    let admin_block_padding = vec![(block_size - 5) as u8; block_size - 5];
-    let admin_block = ["admin".as_bytes(), &admin_block_padding].concat();
-
-    let crafted_ciphertext_block =
-        craft_admin_ciphertext_block(&encrypt_fn, block_size, admin_block);
-
-    let crafted_input = vec![0; original_padding_length + "user".len()];
-    let ciphertext = encrypt_fn(&crafted_input);
-    let ciphertext_without_last_block = ciphertext[..ciphertext.len() - block_size].to_vec();
-
-    [ciphertext_without_last_block, crafted_ciphertext_block].concat()
-}
-
-fn craft_admin_ciphertext_block<F>(
-    encrypt_fn: F,
-    block_size: usize,
-    admin_block: Vec<u8>,
-) -> Vec<u8>
-where
-    F: Fn(&[u8]) -> Vec<u8>,
-{
-    let mut i = 0;
-    loop {
-        let crafted_input = [vec![0; i], admin_block.clone(), admin_block.clone()].concat();
-        let ciphertext = encrypt_fn(&crafted_input);
-        let ciphertext_blocks = ciphertext.chunks(block_size).collect::<Vec<&[u8]>>();
-
-        for j in 1..ciphertext_blocks.len() {
-            if ciphertext_blocks[j - 1] == ciphertext_blocks[j] {
-                return ciphertext_blocks[j].to_vec();
-            }
-        }
-
-        i += 1;
-    }
-}
+pub use crate::{
+    attacks::ecb_cut_and_paste_attack,
+    oracles::{
+        profile::{
+            decrypt_profile, encrypt_profile, parse_query_string, profile_for, ProfileOracle,
+        },
+        DecryptionOracle,
+    },
+};
 
 #[cfg(test)]
 mod tests {
-    use crate::set2::challenge11::random_bytes;
+    use std::collections::HashMap;
+
+    use crate::{
+        block::encrypt_aes_ecb, error::CryptopalsError, random::SeededRng,
+        set2::challenge11::random_bytes,
+    };
 
     use super::*;
 
@@ -89,7 +27,13 @@ mod tests {
             ("baz".to_string(), "qux".to_string()),
             ("zap".to_string(), "zazzle".to_string()),
         ]);
-        assert_eq!(expected, parse_query_string(query_string));
+        assert_eq!(expected, parse_query_string(query_string).unwrap());
+        assert_eq!(
+            Err(CryptopalsError::InvalidQueryString(
+                "foo=bar&baz".to_string()
+            )),
+            parse_query_string("foo=bar&baz")
+        );
     }
 
     #[test]
@@ -106,14 +50,45 @@ mod tests {
 
     #[test]
     fn test_ecb_cut_and_paste_attack() {
-        let key = random_bytes(16);
+        let mut rng = SeededRng::from_env();
+        let oracle = ProfileOracle::new(&mut rng);
+        let crafted_ciphertext = ecb_cut_and_paste_attack(&oracle).unwrap();
+
+        let profile = oracle.decrypt(&crafted_ciphertext).unwrap();
+        assert_eq!("admin", profile.get("role").unwrap());
+    }
+
+    #[test]
+    fn test_ecb_cut_and_paste_attack_with_free_functions() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
 
         let encrypt_fn = |data: &[u8]| {
-            encrypt_profile(&data.iter().map(|&x| x as char).collect::<String>(), &key)
+            encrypt_profile(&data.iter().map(|&x| x as char).collect::<String>(), &key).unwrap()
         };
-        let crafted_ciphertext = ecb_cut_and_paste_attack(encrypt_fn);
+        let crafted_ciphertext = ecb_cut_and_paste_attack(&encrypt_fn).unwrap();
 
-        let profile = decrypt_profile(&crafted_ciphertext, &key);
+        let profile = decrypt_profile(&crafted_ciphertext, &key).unwrap();
         assert_eq!("admin", profile.get("role").unwrap());
     }
+
+    #[test]
+    fn test_ecb_cut_and_paste_attack_gives_up() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+
+        // Drops everything but zeros, so the admin blocks never reach the ciphertext.
+        let encrypt_fn = |data: &[u8]| {
+            let zeros = data
+                .iter()
+                .filter(|&&x| x == 0)
+                .copied()
+                .collect::<Vec<u8>>();
+            encrypt_aes_ecb(&zeros, &key).unwrap()
+        };
+        assert!(matches!(
+            ecb_cut_and_paste_attack(&encrypt_fn),
+            Err(CryptopalsError::OracleFailure(_))
+        ));
+    }
 }
@@ -1,59 +1,62 @@
-use crate::set2::{
-    challenge10::encrypt_aes_128_cbc, challenge11::random_bytes, challenge14::prefix_length,
+pub use crate::{
+    attacks::cbc_bitflipping_attack,
+    oracles::{
+        user_data::{encrypt_user_data, is_admin, UserDataOracle},
+        DecryptionOracle, EncryptionOracle,
+    },
 };
 
-use super::{challenge10::decrypt_aes_128_cbc, challenge12::compute_block_size_and_padding_length};
-
-lazy_static::lazy_static! {
-    static ref IV: Vec<u8> = random_bytes(16);
-    static ref KEY: Vec<u8> = random_bytes(16);
-}
-
-pub fn encrypt_user_data(data: &str) -> Vec<u8> {
-    let data = format!(
-        "comment1=cooking%20MCs;userdata={};comment2=%20like%20a%20pound%20of%20bacon",
-        data
-    );
-    let url_encoded_data = data
-        .replace('=', "%3D")
-        .replace(';', "%3B")
-        .replace(' ', "%20");
-
-    encrypt_aes_128_cbc(url_encoded_data.as_bytes(), &KEY, Some(IV.to_vec()))
-}
-
-pub fn is_admin(data: &[u8]) -> bool {
-    let plain = decrypt_aes_128_cbc(data, &KEY, Some(IV.to_vec()));
-    plain.contains(";admin=true;")
-}
-
-pub fn cbc_bitflipping_attack() -> Vec<u8> {
-    let encrypt_fn = |data: &[u8]| encrypt_user_data(&String::from_utf8_lossy(data));
-    let (block_size, _) = compute_block_size_and_padding_length(encrypt_fn);
-
-    let full_prefix_length = prefix_length(encrypt_fn, block_size);
-    let block_prefix_length = block_size - (full_prefix_length % block_size);
-
-    let mut crafted_input = vec!["a"; block_prefix_length].join("");
-    crafted_input.push_str("XadminYtrueX");
-
-    let ciphertext = encrypt_user_data(&crafted_input);
-
-    let mut crafted_ciphertext = ciphertext;
-    crafted_ciphertext[2 * block_size] ^= b';' ^ b'X';
-    crafted_ciphertext[2 * block_size + 6] ^= b'=' ^ b'Y';
-    crafted_ciphertext[2 * block_size + 11] ^= b';' ^ b'X';
-
-    crafted_ciphertext
-}
-
 #[cfg(test)]
 mod tests {
+    use crate::{
+        block::{decrypt_aes_cbc, encrypt_aes_cbc},
+        random::SeededRng,
+        set2::challenge11::random_bytes,
+    };
+
     use super::*;
 
     #[test]
     fn test_cbc_bitflipping_attack() {
-        let crafted_ciphertext = cbc_bitflipping_attack();
-        assert!(is_admin(&crafted_ciphertext));
+        let mut rng = SeededRng::from_env();
+        let oracle = UserDataOracle::new(&mut rng);
+        let crafted_ciphertext = cbc_bitflipping_attack(&oracle).unwrap();
+        assert!(oracle.decrypt(&crafted_ciphertext).unwrap());
+    }
+
+    #[test]
+    fn test_user_data_is_escaped() {
+        let mut rng = SeededRng::from_env();
+        let ciphertext = encrypt_user_data(";admin=true;", &[0; 16], &[0; 16]).unwrap();
+        assert!(!is_admin(&ciphertext, &[0; 16], &[0; 16]).unwrap());
+
+        let oracle = UserDataOracle::new(&mut rng);
+        let ciphertext = oracle.encrypt(b";admin=true;");
+        assert!(!oracle.decrypt(&ciphertext).unwrap());
+    }
+
+    #[test]
+    fn test_cbc_bitflipping_attack_any_prefix_length() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        for prefix_length in 0..48 {
+            let prefix = vec![b'p'; prefix_length];
+            let oracle = |data: &[u8]| {
+                let escaped = String::from_utf8_lossy(data)
+                    .replace(';', "%3B")
+                    .replace('=', "%3D");
+                let plain = [&prefix, escaped.as_bytes(), b";comment2=bacon"].concat();
+                encrypt_aes_cbc(&plain, &key, None).unwrap()
+            };
+
+            let crafted_ciphertext = cbc_bitflipping_attack(&oracle).unwrap();
+            let plain = decrypt_aes_cbc(&crafted_ciphertext, &key, None).unwrap();
+            assert_eq!(
+                b";admin=true;",
+                &plain[prefix_length.div_ceil(16) * 16 + 16..][..12],
+                "Failed for {prefix_length}"
+            );
+            assert_eq!(prefix, plain[..prefix_length]);
+        }
     }
 }
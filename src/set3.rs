@@ -0,0 +1,6 @@
+pub mod challenge17;
+
+#[cfg(test)]
+fn read_set3_resource(filename: &str) -> String {
+    crate::read_resource("set3", filename)
+}
@@ -0,0 +1,45 @@
+pub use crate::{
+    attacks::cbc_padding_oracle_attack,
+    oracles::padding::{encrypt, has_valid_padding},
+};
+
+#[cfg(test)]
+mod tests {
+    use base64::prelude::*;
+
+    use crate::{
+        error::CryptopalsError, oracles::random_bytes, random::SeededRng, set3::read_set3_resource,
+    };
+
+    use super::*;
+
+    #[test]
+    fn padding_oracle_attack_works() {
+        let mut rng = SeededRng::from_env();
+        let key = random_bytes(&mut rng, 16);
+        let oracle = |iv: &[u8], ciphertext: &[u8]| has_valid_padding(iv, ciphertext, &key);
+
+        for line in read_set3_resource("challenge17.txt").lines() {
+            let plain = BASE64_STANDARD.decode(line).unwrap();
+            let (iv, ciphertext) = encrypt(&mut rng, &plain, &key).unwrap();
+
+            assert_eq!(
+                plain,
+                cbc_padding_oracle_attack(oracle, &iv, &ciphertext, 16).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn padding_oracle_attack_rejects_bad_input() {
+        let oracle = |_: &[u8], _: &[u8]| false;
+        assert_eq!(
+            Err(CryptopalsError::InvalidDataLength(15)),
+            cbc_padding_oracle_attack(oracle, &[0; 16], &[0; 15], 16)
+        );
+        assert!(matches!(
+            cbc_padding_oracle_attack(oracle, &[0; 16], &[0; 16], 16),
+            Err(CryptopalsError::OracleFailure(_))
+        ));
+    }
+}
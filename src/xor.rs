@@ -0,0 +1,140 @@
+use base64::prelude::*;
+
+use crate::error::CryptopalsError;
+
+mod repeating_key;
+
+pub use repeating_key::{RepeatingKeyXor, RepeatingKeyXorReader, RepeatingKeyXorWriter};
+
+pub fn xor_hex(a: &str, b: &str) -> Result<String, CryptopalsError> {
+    let a_bytes = hex::decode(a)?;
+    let b_bytes = hex::decode(b)?;
+
+    Ok(hex::encode(xor_bytes(&a_bytes, &b_bytes)))
+}
+
+pub fn xor_base64(a: &str, b: &str) -> Result<String, CryptopalsError> {
+    let a_bytes = BASE64_STANDARD.decode(a)?;
+    let b_bytes = BASE64_STANDARD.decode(b)?;
+
+    Ok(BASE64_STANDARD.encode(xor_bytes(&a_bytes, &b_bytes)))
+}
+
+/// Xors the common prefix of both inputs, silently dropping the rest of the longer one.
+pub fn xor_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect::<Vec<u8>>()
+}
+
+pub fn xor_bytes_strict(a: &[u8], b: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    if a.len() != b.len() {
+        return Err(CryptopalsError::MismatchedLengths(a.len(), b.len()));
+    }
+
+    Ok(xor_bytes(a, b))
+}
+
+/// Xors `src` into `dst`.
+///
+/// Panics if the lengths differ, like `copy_from_slice`.
+pub fn xor_into(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "xor_into needs equal lengths");
+
+    for (dst_byte, src_byte) in dst.iter_mut().zip(src) {
+        *dst_byte ^= src_byte;
+    }
+}
+
+pub fn xor_cycle(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    let mut result = data.to_vec();
+    xor_cycle_into(&mut result, key)?;
+    Ok(result)
+}
+
+/// Xors `data` in place with `key` repeated as many times as needed.
+pub fn xor_cycle_into(data: &mut [u8], key: &[u8]) -> Result<(), CryptopalsError> {
+    if key.is_empty() {
+        return Err(CryptopalsError::InvalidKeyLength(0));
+    }
+
+    // Whole keys only, so every chunk starts at the beginning of the key.
+    let key_stream = key.repeat(512usize.div_ceil(key.len()));
+    for chunk in data.chunks_mut(key_stream.len()) {
+        let length = chunk.len();
+        xor_into(chunk, &key_stream[..length]);
+    }
+
+    Ok(())
+}
+
+pub fn encrypt_repeating_key_xor_to_hex(
+    data: &[u8],
+    key: &[u8],
+) -> Result<String, CryptopalsError> {
+    Ok(hex::encode(RepeatingKeyXor::new(key)?.apply(data)))
+}
+
+pub fn encrypt_repeating_key_xor_to_base64(
+    data: &[u8],
+    key: &[u8],
+) -> Result<String, CryptopalsError> {
+    Ok(BASE64_STANDARD.encode(RepeatingKeyXor::new(key)?.apply(data)))
+}
+
+pub fn decrypt_repeating_xor_hex(data: &str, key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    let data_bytes = hex::decode(data)?;
+    decrypt_repeating_xor_bytes(&data_bytes, key)
+}
+
+pub fn decrypt_repeating_xor_base64(data: &str, key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    let data_bytes = BASE64_STANDARD.decode(data)?;
+    decrypt_repeating_xor_bytes(&data_bytes, key)
+}
+
+pub fn decrypt_repeating_xor_bytes(data: &[u8], key: &[u8]) -> Result<Vec<u8>, CryptopalsError> {
+    Ok(RepeatingKeyXor::new(key)?.apply(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xor_variants_agree() {
+        let a = (0..=255u8).cycle().take(1001).collect::<Vec<u8>>();
+        let b = a
+            .iter()
+            .rev()
+            .map(|byte| byte.wrapping_mul(7))
+            .collect::<Vec<u8>>();
+        let expected = a.iter().zip(&b).map(|(a, b)| a ^ b).collect::<Vec<u8>>();
+
+        assert_eq!(expected, xor_bytes(&a, &b));
+        assert_eq!(Ok(expected.clone()), xor_bytes_strict(&a, &b));
+
+        let mut dst = a.clone();
+        xor_into(&mut dst, &b);
+        assert_eq!(expected, dst);
+    }
+
+    #[test]
+    fn xor_bytes_strict_rejects_mismatched_lengths() {
+        assert_eq!(
+            Err(CryptopalsError::MismatchedLengths(3, 2)),
+            xor_bytes_strict(&[1, 2, 3], &[1, 2])
+        );
+        assert_eq!(vec![0, 0], xor_bytes(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn xor_cycle_works() {
+        let data = vec![0u8; 1000];
+        let key = b"ICE";
+        let expected = key.iter().cycle().take(1000).copied().collect::<Vec<u8>>();
+
+        assert_eq!(Ok(expected), xor_cycle(&data, key));
+        assert_eq!(
+            Err(CryptopalsError::InvalidKeyLength(0)),
+            xor_cycle(&data, &[])
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::{self, Read, Write};
+
+use crate::error::CryptopalsError;
+
+use super::xor_cycle_into;
+
+/// Repeating-key XOR. Encryption and decryption are the same operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepeatingKeyXor {
+    key: Vec<u8>,
+}
+
+impl RepeatingKeyXor {
+    pub fn new(key: &[u8]) -> Result<Self, CryptopalsError> {
+        if key.is_empty() {
+            return Err(CryptopalsError::InvalidKeyLength(0));
+        }
+
+        Ok(Self { key: key.to_vec() })
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn apply(&self, data: &[u8]) -> Vec<u8> {
+        self.apply_at(data, 0)
+    }
+
+    /// Same as `apply`, but the first byte of `data` is xored with the key byte at `offset`.
+    pub fn apply_at(&self, data: &[u8], offset: usize) -> Vec<u8> {
+        let mut data = data.to_vec();
+        self.apply_in_place(&mut data, offset);
+        data
+    }
+
+    pub fn apply_in_place(&self, data: &mut [u8], offset: usize) {
+        let mut key = self.key.clone();
+        key.rotate_left(offset % self.key.len());
+        xor_cycle_into(data, &key).expect("Key is not empty");
+    }
+
+    pub fn reader<R: Read>(&self, inner: R, offset: usize) -> RepeatingKeyXorReader<R> {
+        RepeatingKeyXorReader {
+            cipher: self.clone(),
+            inner,
+            offset,
+        }
+    }
+
+    pub fn writer<W: Write>(&self, inner: W, offset: usize) -> RepeatingKeyXorWriter<W> {
+        RepeatingKeyXorWriter {
+            cipher: self.clone(),
+            inner,
+            offset,
+        }
+    }
+}
+
+/// Xors everything read from the inner reader, continuing the key where the last read stopped.
+#[derive(Debug)]
+pub struct RepeatingKeyXorReader<R> {
+    cipher: RepeatingKeyXor,
+    inner: R,
+    offset: usize,
+}
+
+impl<R> RepeatingKeyXorReader<R> {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for RepeatingKeyXorReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let num_bytes = self.inner.read(buf)?;
+        self.cipher
+            .apply_in_place(&mut buf[..num_bytes], self.offset);
+        self.offset = (self.offset + num_bytes) % self.cipher.key.len();
+
+        Ok(num_bytes)
+    }
+}
+
+/// Xors everything written before passing it to the inner writer.
+#[derive(Debug)]
+pub struct RepeatingKeyXorWriter<W> {
+    cipher: RepeatingKeyXor,
+    inner: W,
+    offset: usize,
+}
+
+impl<W> RepeatingKeyXorWriter<W> {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for RepeatingKeyXorWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let xored = self.cipher.apply_at(buf, self.offset);
+        let num_bytes = self.inner.write(&xored)?;
+        self.offset = (self.offset + num_bytes) % self.cipher.key.len();
+
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: &[u8] = b"Burning 'em, if you ain't quick and nimble";
+
+    #[test]
+    fn repeating_key_xor_is_symmetric() {
+        let cipher = RepeatingKeyXor::new(&[0x00, 0xff, 0x80]).unwrap();
+        let encrypted = cipher.apply(PLAIN);
+        assert_ne!(PLAIN, encrypted);
+        assert_eq!(PLAIN, cipher.apply(&encrypted));
+        assert_eq!(encrypted[4..], cipher.apply_at(&PLAIN[4..], 4));
+        assert_eq!(
+            Err(CryptopalsError::InvalidKeyLength(0)),
+            RepeatingKeyXor::new(&[])
+        );
+    }
+
+    #[test]
+    fn repeating_key_xor_streams() {
+        let cipher = RepeatingKeyXor::new(b"ICE").unwrap();
+        let encrypted = cipher.apply(PLAIN);
+
+        let mut writer = cipher.writer(vec![], 0);
+        for chunk in PLAIN.chunks(5) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(encrypted, writer.into_inner());
+
+        let mut reader = cipher.reader(&encrypted[1..], 1);
+        let mut decrypted = vec![];
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(PLAIN[1..], decrypted);
+    }
+}